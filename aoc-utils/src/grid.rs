@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut, Range};
+
+use crate::Point;
+
+// which way y goes when a grid's read from text or drawn as text. the
+// puzzles' maps go down the screen, but directions have up as y increasing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum YAxis {
+    Down,
+    Up,
+}
+
+impl YAxis {
+    // the y of each line of text, from the top
+    fn lines(self, bounds: Bounds) -> Vec<isize> {
+        match self {
+            YAxis::Down => bounds.ys().collect(),
+            YAxis::Up => bounds.ys().rev().collect(),
+        }
+    }
+
+    fn y(self, line: usize) -> isize {
+        match self {
+            YAxis::Down => line as isize,
+            YAxis::Up => -(line as isize),
+        }
+    }
+}
+
+// the rectangle from min up to but not including max
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    pub fn new(width: usize, height: usize) -> Self {
+        Bounds {
+            min: Point::origin(),
+            max: Point(width as isize, height as isize),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.0 - self.min.0).max(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.1 - self.min.1).max(0) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width() == 0 || self.height() == 0
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.0..self.max.0).contains(&p.0) && (self.min.1..self.max.1).contains(&p.1)
+    }
+
+    // grows the bounds to cover the point
+    pub fn include(&mut self, p: Point) {
+        if self.is_empty() {
+            *self = Bounds {
+                min: p,
+                max: p + Point(1, 1),
+            };
+            return;
+        }
+        self.min = Point(self.min.0.min(p.0), self.min.1.min(p.1));
+        self.max = Point(self.max.0.max(p.0 + 1), self.max.1.max(p.1 + 1));
+    }
+
+    pub fn xs(&self) -> Range<isize> {
+        self.min.0..self.max.0
+    }
+
+    pub fn ys(&self) -> Range<isize> {
+        self.min.1..self.max.1
+    }
+
+    // every point inside, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let xs = self.xs();
+        self.ys()
+            .flat_map(move |y| xs.clone().map(move |x| Point(x, y)))
+    }
+
+    // a line of text for each row, a character for each point
+    pub fn render(&self, y: YAxis, tile: impl Fn(Point) -> char) -> String {
+        let mut text = String::new();
+        for y in y.lines(*self) {
+            text.extend(self.xs().map(|x| tile(Point(x, y))));
+            text.push('\n');
+        }
+        text
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        if !self.contains(p) {
+            return None;
+        }
+        Some((p.1 - self.min.1) as usize * self.width() + (p.0 - self.min.0) as usize)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    Width { line: usize, width: usize },
+    Tile { line: usize, tile: char },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Width { line, width } => {
+                write!(f, "line {} isn't {} tiles wide", line, width)
+            }
+            ParseError::Tile { line, tile } => write!(f, "unexpected {:?} on line {}", tile, line),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// the lines of a map, leaving off any blank ones at the end
+fn lines(input: &str) -> Vec<&str> {
+    input.trim_end_matches(['\n', '\r']).lines().collect()
+}
+
+// a value for every point of a rectangle, stored row by row
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    bounds: Bounds,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::with_bounds(Bounds::new(width, height), fill)
+    }
+
+    pub fn with_bounds(bounds: Bounds, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            bounds,
+            cells: vec![fill; bounds.width() * bounds.height()],
+        }
+    }
+
+    // each character turned into a tile, every line the same width
+    pub fn parse(
+        input: &str,
+        y: YAxis,
+        tile: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let lines = lines(input);
+        let width = lines.first().map_or(0, |x| x.chars().count());
+        let mut rows = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(ParseError::Width { line: i + 1, width });
+            }
+            let row = line
+                .chars()
+                .map(|c| {
+                    tile(c).ok_or(ParseError::Tile {
+                        line: i + 1,
+                        tile: c,
+                    })
+                })
+                .collect::<Result<Vec<T>, _>>()?;
+            rows.push(row);
+        }
+
+        // with y going up, the first line's still y = 0, so the rest of
+        // them are below it and the cells start from the last line
+        let height = rows.len() as isize;
+        let min = match y {
+            YAxis::Down => Point::origin(),
+            YAxis::Up => {
+                rows.reverse();
+                Point(0, 1 - height)
+            }
+        };
+        Ok(Grid {
+            bounds: Bounds {
+                min,
+                max: min + Point(width as isize, height),
+            },
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds.height()
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.bounds.contains(p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.bounds.index(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.bounds.index(p).map(move |i| &mut self.cells[i])
+    }
+
+    // every point with its tile, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.bounds.points().zip(self.cells.iter())
+    }
+
+    // from the row with the smallest y
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width().max(1))
+    }
+
+    pub fn row(&self, y: isize) -> Option<&[T]> {
+        let start = self.bounds.index(Point(self.bounds.min.0, y))?;
+        Some(&self.cells[start..start + self.width()])
+    }
+
+    pub fn column(&self, x: isize) -> impl Iterator<Item = &T> {
+        let start = self.bounds.index(Point(x, self.bounds.min.1));
+        let cells = start.map_or(&self.cells[..0], |i| &self.cells[i..]);
+        cells.iter().step_by(self.width().max(1))
+    }
+
+    // the adjacent points inside the grid
+    pub fn neighbours(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.adjacent()
+            .into_iter()
+            .filter_map(move |x| self.get(x).map(|tile| (x, tile)))
+    }
+
+    pub fn render(&self, y: YAxis, tile: impl Fn(Point, &T) -> char) -> String {
+        self.bounds.render(y, |p| tile(p, &self[p]))
+    }
+
+    // only the tiles worth keeping, over the same bounds
+    pub fn into_sparse(self, keep: impl Fn(&T) -> bool) -> SparseGrid<T> {
+        let bounds = self.bounds;
+        let cells = bounds
+            .points()
+            .zip(self.cells)
+            .filter(|(_, x)| keep(x))
+            .collect();
+        SparseGrid { bounds, cells }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", p))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", p))
+    }
+}
+
+// only the points with something there, for maps which are mostly empty
+// or which get discovered bit by bit. the bounds grow to cover every point
+// inserted
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    bounds: Bounds,
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            bounds: Bounds::default(),
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid::default()
+    }
+
+    // like Grid::parse, but characters without a tile are left empty, and
+    // the lines can be different widths
+    pub fn parse(input: &str, y: YAxis, tile: impl Fn(char) -> Option<T>) -> Self {
+        let mut grid = SparseGrid::new();
+        for (i, line) in lines(input).iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(tile) = tile(c) {
+                    grid.insert(Point(x as isize, y.y(i)), tile);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn insert(&mut self, p: Point, tile: T) -> Option<T> {
+        self.bounds.include(p);
+        self.cells.insert(p, tile)
+    }
+
+    // in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, x)| (p, x))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    // the tiles in a row, from left to right
+    pub fn row(&self, y: isize) -> impl Iterator<Item = (Point, &T)> {
+        self.bounds
+            .xs()
+            .filter_map(move |x| self.get(Point(x, y)).map(|tile| (Point(x, y), tile)))
+    }
+
+    // the tiles in a column, from the smallest y
+    pub fn column(&self, x: isize) -> impl Iterator<Item = (Point, &T)> {
+        self.bounds
+            .ys()
+            .filter_map(move |y| self.get(Point(x, y)).map(|tile| (Point(x, y), tile)))
+    }
+
+    // the adjacent points with something there
+    pub fn neighbours(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.adjacent()
+            .into_iter()
+            .filter_map(move |x| self.get(x).map(|tile| (x, tile)))
+    }
+
+    pub fn render(&self, y: YAxis, tile: impl Fn(Point, Option<&T>) -> char) -> String {
+        self.bounds.render(y, |p| tile(p, self.get(p)))
+    }
+
+    // the empty points filled in
+    pub fn into_dense(mut self, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut grid = Grid::with_bounds(self.bounds, fill);
+        for p in self.bounds.points() {
+            if let Some(tile) = self.cells.remove(&p) {
+                grid[p] = tile;
+            }
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const MAP: &str = "#.#
+..#
+";
+
+    #[test]
+    fn test_parse() {
+        use super::{Grid, ParseError, Point, YAxis};
+
+        let tile = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let grid = Grid::parse(MAP, YAxis::Down, tile).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[Point(2, 1)]);
+        assert_eq!(grid.get(Point(0, 1)), Some(&false));
+        assert_eq!(grid.get(Point(0, 2)), None);
+
+        // the top line is still y = 0, with the rest below it
+        let up = Grid::parse(MAP, YAxis::Up, tile).unwrap();
+        assert_eq!(up.bounds().min, Point(0, -1));
+        assert!(up[Point(0, 0)] && !up[Point(1, 0)] && !up[Point(0, -1)]);
+
+        assert_eq!(
+            Grid::parse("#.\n#", YAxis::Down, tile),
+            Err(ParseError::Width { line: 2, width: 2 })
+        );
+        assert_eq!(
+            Grid::parse("#x", YAxis::Down, tile),
+            Err(ParseError::Tile { line: 1, tile: 'x' })
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        use super::{Grid, Point, YAxis};
+
+        let grid = Grid::parse("abc\ndef", YAxis::Down, Some).unwrap();
+        let rows: Vec<String> = grid.rows().map(|x| x.iter().collect()).collect();
+        assert_eq!(rows, vec!["abc", "def"]);
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(3).count(), 0);
+
+        let mut neighbours: Vec<char> = grid.neighbours(Point(1, 0)).map(|(_, &x)| x).collect();
+        neighbours.sort();
+        assert_eq!(neighbours, vec!['a', 'c', 'e']);
+    }
+
+    #[test]
+    fn test_sparse() {
+        use super::{Bounds, Grid, Point, SparseGrid, YAxis};
+
+        let mut grid =
+            SparseGrid::parse(
+                "#\n.##",
+                YAxis::Up,
+                |c| if c == '#' { Some(c) } else { None },
+            );
+        assert_eq!(grid.len(), 3);
+        assert!(grid.contains(Point(0, 0)) && grid.contains(Point(2, -1)));
+        assert_eq!(
+            grid.bounds(),
+            Bounds {
+                min: Point(0, -1),
+                max: Point(3, 1)
+            }
+        );
+        assert_eq!(grid.neighbours(Point(1, -1)).count(), 1);
+        assert_eq!(
+            grid.row(-1).map(|(p, _)| p.0).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(grid.column(0).count(), 1);
+
+        grid.insert(Point(-1, 1), '@');
+        let render = |_, x: Option<&char>| *x.unwrap_or(&' ');
+        assert_eq!(grid.render(YAxis::Up, render), "@   \n #  \n  ##\n");
+        assert_eq!(grid.render(YAxis::Down, render), "  ##\n #  \n@   \n");
+
+        // going dense fills in the gaps, and going back empties them again
+        let dense = grid.clone().into_dense('.');
+        assert_eq!(dense.render(YAxis::Up, |_, &x| x), "@...\n.#..\n..##\n");
+        assert_eq!(dense.iter().filter(|(_, &x)| x == '.').count(), 8);
+        assert_eq!(dense.into_sparse(|&x| x != '.'), grid);
+
+        let dense = Grid::new(2, 2, 0);
+        assert_eq!(dense.clone().into_sparse(|_| true).into_dense(1), dense);
+    }
+}
@@ -0,0 +1,33 @@
+use std::ops::{Div, Rem};
+
+pub mod direction;
+pub mod grid;
+pub mod point;
+pub mod search;
+pub mod svg;
+
+pub use direction::Direction;
+pub use grid::{Bounds, Grid, SparseGrid, YAxis};
+pub use point::{Number, Point, Point3};
+pub use search::{astar, bfs, dijkstra, Search};
+
+// the quotient and remainder in one go, e.g. for splitting an intcode
+// opcode from its parameter modes
+pub fn divmod<T>(value: T, divisor: T) -> (T, T)
+where
+    T: Copy + Div<Output = T> + Rem<Output = T>,
+{
+    (value / divisor, value % divisor)
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_divmod() {
+        use super::divmod;
+
+        assert_eq!(divmod(1002, 100), (10, 2));
+        assert_eq!(divmod(7usize, 10), (0, 7));
+    }
+}
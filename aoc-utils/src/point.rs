@@ -0,0 +1,296 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign};
+
+use crate::Direction;
+
+// the integer types points are made of
+pub trait Number:
+    Copy
+    + Ord
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn distance(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+macro_rules! number {
+    ($($t:ty),*) => {
+        $(
+            impl Number for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+            }
+        )*
+    };
+}
+
+number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// a point on a grid, with y going up. for unsigned points, asking for the
+// neighbours of a point on an axis will overflow
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T = isize>(pub T, pub T);
+
+impl<T: Number> Point<T> {
+    pub fn origin() -> Self {
+        Point(T::ZERO, T::ZERO)
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        self.0.distance(other.0) + self.1.distance(other.1)
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        self.go(direction, T::ONE)
+    }
+
+    pub fn go(self, direction: Direction, steps: T) -> Self {
+        let Point(x, y) = self;
+        match direction {
+            Direction::Up => Point(x, y + steps),
+            Direction::Down => Point(x, y - steps),
+            Direction::Left => Point(x - steps, y),
+            Direction::Right => Point(x + steps, y),
+        }
+    }
+
+    // up, down, right then left
+    pub fn adjacent(self) -> Vec<Self> {
+        let (x, y, one) = (self.0, self.1, T::ONE);
+        vec![
+            Point(x, y + one),
+            Point(x, y - one),
+            Point(x + one, y),
+            Point(x - one, y),
+        ]
+    }
+
+    // the adjacent points and the diagonals
+    pub fn neighbours(self) -> Vec<Self> {
+        let (x, y, one) = (self.0, self.1, T::ONE);
+        let mut neighbours = self.adjacent();
+        neighbours.extend(vec![
+            Point(x + one, y + one),
+            Point(x + one, y - one),
+            Point(x - one, y - one),
+            Point(x - one, y + one),
+        ]);
+        neighbours
+    }
+}
+
+impl<T: Number> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl<T: Number> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+impl<T: Number> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, scale: T) -> Self {
+        Point(self.0 * scale, self.1 * scale)
+    }
+}
+
+impl<T: Number + Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point(-self.0, -self.1)
+    }
+}
+
+impl<T: Number> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Number> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point(x, y)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T = isize>(pub T, pub T, pub T);
+
+impl<T: Number> Point3<T> {
+    pub fn origin() -> Self {
+        Point3(T::ZERO, T::ZERO, T::ZERO)
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        self.0.distance(other.0) + self.1.distance(other.1) + self.2.distance(other.2)
+    }
+
+    // a step either way along each axis
+    pub fn adjacent(self) -> Vec<Self> {
+        let (x, y, z, one) = (self.0, self.1, self.2, T::ONE);
+        vec![
+            Point3(x + one, y, z),
+            Point3(x - one, y, z),
+            Point3(x, y + one, z),
+            Point3(x, y - one, z),
+            Point3(x, y, z + one),
+            Point3(x, y, z - one),
+        ]
+    }
+
+    // everything in the surrounding cube
+    pub fn neighbours(self) -> Vec<Self> {
+        let steps = |n: T| vec![n - T::ONE, n, n + T::ONE];
+        let mut neighbours = vec![];
+        for &x in steps(self.0).iter() {
+            for &y in steps(self.1).iter() {
+                for &z in steps(self.2).iter() {
+                    if Point3(x, y, z) != self {
+                        neighbours.push(Point3(x, y, z));
+                    }
+                }
+            }
+        }
+        neighbours
+    }
+}
+
+impl<T: Number> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point3(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
+}
+
+impl<T: Number> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point3(self.0 - other.0, self.1 - other.1, self.2 - other.2)
+    }
+}
+
+impl<T: Number> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, scale: T) -> Self {
+        Point3(self.0 * scale, self.1 * scale, self.2 * scale)
+    }
+}
+
+impl<T: Number + Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point3(-self.0, -self.1, -self.2)
+    }
+}
+
+impl<T: Number> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Number> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_arithmetic() {
+        use super::{Point, Point3};
+
+        assert_eq!(Point(1, 2) + Point(3, -4), Point(4, -2));
+        assert_eq!(Point(1, 2) - Point(3, -4), Point(-2, 6));
+        assert_eq!(Point(1, -2) * 3, Point(3, -6));
+        assert_eq!(-Point(1, -2), Point(-1, 2));
+        let mut p = Point(5usize, 5);
+        p -= Point(2, 3);
+        p += Point(1, 0);
+        assert_eq!(p, Point(4, 2));
+        assert_eq!(Point(3, 4).manhattan(Point::origin()), 7);
+        assert_eq!(Point(3usize, 4).manhattan(Point(5, 1)), 5);
+
+        assert_eq!(Point3(1, 2, 3) + Point3(1, 1, 1), Point3(2, 3, 4));
+        assert_eq!(Point3(1, 2, 3) - Point3(3, 2, 1), Point3(-2, 0, 2));
+        assert_eq!(Point3(-1, 2, -3).manhattan(Point3::origin()), 6);
+        assert_eq!(Point3::from((1, 2, 3)) * 2, Point3(2, 4, 6));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        use super::{Point, Point3};
+        use std::collections::HashSet;
+
+        let adjacent = Point(0, 0).adjacent();
+        assert_eq!(
+            adjacent,
+            vec![Point(0, 1), Point(0, -1), Point(1, 0), Point(-1, 0)]
+        );
+        let neighbours: HashSet<Point> = Point(0, 0).neighbours().into_iter().collect();
+        assert_eq!(neighbours.len(), 8);
+        assert!(neighbours.iter().all(|x| x.0.abs() <= 1 && x.1.abs() <= 1));
+        assert!(!neighbours.contains(&Point(0, 0)));
+
+        assert_eq!(Point3(0, 0, 0).adjacent().len(), 6);
+        let neighbours: HashSet<_> = Point3(1, 1, 1).neighbours().into_iter().collect();
+        assert_eq!(neighbours.len(), 26);
+        assert!(!neighbours.contains(&Point3(1, 1, 1)));
+    }
+
+    #[test]
+    fn test_step() {
+        use super::Point;
+        use crate::Direction;
+
+        let p = Point(0, 0);
+        assert_eq!(p.step(Direction::Up), Point(0, 1));
+        assert_eq!(p.step(Direction::Down), Point(0, -1));
+        assert_eq!(p.go(Direction::Left, 3), Point(-3, 0));
+        assert_eq!(p.go(Direction::Right, 2), Point(2, 0));
+        for &direction in Direction::ALL.iter() {
+            assert_eq!(p.step(direction).step(direction.reverse()), p);
+        }
+    }
+}
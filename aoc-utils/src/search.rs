@@ -0,0 +1,294 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::Number;
+
+// what a search found: the cost of reaching every node it got to, and the
+// node each one was reached from, so the way back to a start can be found
+#[derive(Clone, Debug)]
+pub struct Search<N, C = usize> {
+    costs: HashMap<N, C>,
+    previous: HashMap<N, N>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new() -> Self {
+        Search {
+            costs: HashMap::new(),
+            previous: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    // the first node reached which was a goal
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    // every node reached. after stopping early at a goal, dijkstra and astar
+    // also leave in the nodes still waiting to be looked at, with the best
+    // cost found for them so far, which mightn't be their cheapest. only the
+    // goal and the nodes looked at before it are final, as is everything
+    // bfs reaches
+    pub fn costs(&self) -> &HashMap<N, C> {
+        &self.costs
+    }
+
+    // from whichever start is closest to the node, to the node itself
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        if !self.costs.contains_key(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.previous.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// the fewest steps to everywhere reachable from any of the starts, or just
+// until a goal comes up
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = search.costs.entry(start.clone()) {
+            entry.insert(0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        let cost = search.costs[&node] + 1;
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = search.costs.entry(next.clone()) {
+                entry.insert(cost);
+                search.previous.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+// the cheapest way to everywhere reachable, with neighbours given along
+// with the cost of moving to them
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Number,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::ZERO, goal)
+}
+
+// dijkstra, but trying the nodes the heuristic thinks are closest to a goal
+// first. the heuristic mustn't ever overestimate the cost of getting from a
+// node to a goal, or past a neighbour to one, or the costs can be wrong
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Number,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        search.costs.insert(start.clone(), C::ZERO);
+        queue.push(Queued {
+            priority: heuristic(&start),
+            cost: C::ZERO,
+            node: start,
+        });
+    }
+
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        // already reached some cheaper way
+        if cost > search.costs[&node] {
+            continue;
+        }
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        for (next, step) in neighbours(&node) {
+            let cost = cost + step;
+            if search.costs.get(&next).is_some_and(|&x| x <= cost) {
+                continue;
+            }
+            search.costs.insert(next.clone(), cost);
+            search.previous.insert(next.clone(), node.clone());
+            queue.push(Queued {
+                priority: cost + heuristic(&next),
+                cost,
+                node: next,
+            });
+        }
+    }
+    search
+}
+
+// a node waiting in the queue, with the cheapest priority coming out first
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+#[cfg(test)]
+mod test {
+
+    // a five by five square split by a wall, with a gap at the bottom
+    fn open(p: &crate::Point) -> Vec<crate::Point> {
+        p.adjacent()
+            .into_iter()
+            .filter(|x| (0..5).contains(&x.0) && (0..5).contains(&x.1))
+            .filter(|x| x.0 != 2 || x.1 == 4)
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        use super::bfs;
+        use crate::Point;
+
+        let search = bfs(vec![Point(0, 0)], open, |_| false);
+        assert_eq!(search.costs().len(), 21);
+        assert_eq!(search.cost(&Point(4, 0)), Some(12));
+        assert_eq!(search.cost(&Point(2, 0)), None);
+        assert_eq!(search.goal(), None);
+
+        let path = search.path(&Point(4, 0)).unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), (Point(0, 0), Point(4, 0)));
+        assert!(path.windows(2).all(|x| x[0].manhattan(x[1]) == 1));
+        assert!(path.contains(&Point(2, 4)));
+        assert_eq!(search.path(&Point(2, 0)), None);
+        assert_eq!(search.path(&Point(0, 0)), Some(vec![Point(0, 0)]));
+    }
+
+    #[test]
+    fn test_early_exit() {
+        use super::bfs;
+        use crate::Point;
+
+        let search = bfs(vec![Point(0, 0)], open, |&x| x == Point(0, 3));
+        assert_eq!(search.goal(), Some(&Point(0, 3)));
+        assert_eq!(search.cost(&Point(0, 3)), Some(3));
+        assert_eq!(search.cost(&Point(4, 0)), None);
+
+        // a start which is already a goal
+        let search = bfs(vec![Point(0, 0)], open, |_| true);
+        assert_eq!(search.goal(), Some(&Point(0, 0)));
+        assert_eq!(search.costs().len(), 1);
+    }
+
+    #[test]
+    fn test_multiple_starts() {
+        use super::bfs;
+        use crate::Point;
+
+        // each side fills from its own corner, meeting in the gap
+        let search = bfs(vec![Point(0, 0), Point(4, 0)], open, |_| false);
+        assert_eq!(search.costs().values().max(), Some(&6));
+        assert_eq!(search.cost(&Point(4, 0)), Some(0));
+        assert_eq!(search.path(&Point(4, 0)), Some(vec![Point(4, 0)]));
+        assert_eq!(search.path(&Point(4, 2)).unwrap()[0], Point(4, 0));
+
+        // starting twice from the same place is the same as once
+        let twice = bfs(vec![Point(0, 0), Point(0, 0)], open, |_| false);
+        let once = bfs(vec![Point(0, 0)], open, |_| false);
+        assert_eq!(twice.costs(), once.costs());
+    }
+
+    #[test]
+    fn test_weighted() {
+        use super::{astar, bfs, dijkstra};
+        use crate::Point;
+
+        // going right is cheap, and everything else is dear
+        let neighbours = |p: &Point| {
+            let p = *p;
+            p.adjacent()
+                .into_iter()
+                .filter(|x| (0..10).contains(&x.0) && (0..10).contains(&x.1))
+                .map(move |x| (x, if x.0 > p.0 { 1 } else { 3 }))
+        };
+        let target = Point(9, 9);
+        let goal = |x: &Point| *x == target;
+
+        let slow = dijkstra(vec![Point(0, 0)], neighbours, goal);
+        assert_eq!(slow.cost(&target), Some(9 + 27));
+        let heuristic = |x: &Point| x.manhattan(target);
+        let fast = astar(vec![Point(0, 0)], neighbours, heuristic, goal);
+        assert_eq!(fast.cost(&target), Some(9 + 27));
+        assert!(fast.costs().len() <= slow.costs().len());
+
+        let path = fast.path(&target).unwrap();
+        assert_eq!(path.len(), 19);
+        assert!(path.windows(2).all(|x| x[0].0 <= x[1].0));
+
+        // with every step costing the same, dijkstra agrees with bfs
+        let all = dijkstra(
+            vec![Point(0, 0), Point(9, 0)],
+            |p: &Point| neighbours(p).map(|(x, _)| (x, 1)),
+            |_| false,
+        );
+        let plain = bfs(
+            vec![Point(0, 0), Point(9, 0)],
+            |p: &Point| neighbours(p).map(|(x, _)| x),
+            |_| false,
+        );
+        assert_eq!(all.costs(), plain.costs());
+    }
+}
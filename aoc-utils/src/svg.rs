@@ -0,0 +1,66 @@
+// the colours lines take turns to be drawn in
+pub const COLOURS: [&str; 7] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2",
+];
+
+pub fn colour(i: usize) -> &'static str {
+    COLOURS[i % COLOURS.len()]
+}
+
+// maps a range of values onto an area of a picture, given by its top left
+// corner and its size
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frame {
+    min: (f64, f64),
+    max: (f64, f64),
+    corner: (f64, f64),
+    scale: (f64, f64),
+}
+
+impl Frame {
+    pub fn new(min: (f64, f64), max: (f64, f64), corner: (f64, f64), size: (f64, f64)) -> Self {
+        // a flat range is widened, rather than dividing by zero
+        let span = ((max.0 - min.0).max(1.0), (max.1 - min.1).max(1.0));
+        Frame {
+            min,
+            max: (min.0 + span.0, min.1 + span.1),
+            corner,
+            scale: (size.0 / span.0, size.1 / span.1),
+        }
+    }
+
+    pub fn x(&self, x: f64) -> f64 {
+        self.corner.0 + (x - self.min.0) * self.scale.0
+    }
+
+    // svg y runs downwards, so it's flipped to draw larger values nearer
+    // the top
+    pub fn y(&self, y: f64) -> f64 {
+        self.corner.1 + (self.max.1 - y) * self.scale.1
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_frame() {
+        use super::Frame;
+
+        let frame = Frame::new((-2.0, 0.0), (2.0, 10.0), (5.0, 5.0), (8.0, 100.0));
+        assert_eq!((frame.x(-2.0), frame.x(2.0)), (5.0, 13.0));
+        assert_eq!((frame.y(10.0), frame.y(0.0)), (5.0, 105.0));
+
+        // nothing to spread out, so everything's drawn along the edge
+        let flat = Frame::new((1.0, 3.0), (1.0, 3.0), (0.0, 0.0), (0.0, 0.0));
+        assert_eq!((flat.x(1.0), flat.y(3.0)), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_colour() {
+        use super::{colour, COLOURS};
+
+        assert_eq!(colour(0), "#1f77b4");
+        assert_eq!(colour(COLOURS.len() + 1), colour(1));
+    }
+}
@@ -0,0 +1,197 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+// an answer to print, or why there isn't one
+pub type Answer = Result<String, Box<dyn Error>>;
+
+// a day's puzzle: the input's parsed once, then shared by both parts
+pub trait Solution {
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>>;
+
+    fn part1(&self, input: &Self::Input) -> Answer;
+
+    fn part2(&self, input: &Self::Input) -> Answer;
+}
+
+pub struct Timed {
+    pub part: u8,
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+pub struct Report {
+    pub parse: Duration,
+    pub parts: Vec<Timed>,
+}
+
+impl Report {
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.iter().map(|x| x.time).sum::<Duration>()
+    }
+}
+
+impl fmt::Display for Timed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let answer = match &self.answer {
+            Ok(answer) => answer.clone(),
+            Err(e) => format!("error: {}", e),
+        };
+        // answers which are pictures go on the lines below
+        if answer.contains('\n') {
+            writeln!(f, "part {} ({:?}):", self.part, self.time)?;
+            write!(f, "{}", answer)?;
+            if !answer.ends_with('\n') {
+                writeln!(f)?;
+            }
+            Ok(())
+        } else {
+            writeln!(f, "part {}: {} ({:?})", self.part, answer, self.time)
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "parse ({:?})", self.parse)?;
+        for timed in self.parts.iter() {
+            write!(f, "{}", timed)?;
+        }
+        Ok(())
+    }
+}
+
+// any solution, whatever its parsed input looks like, so they can all be
+// kept together
+pub trait Run {
+    fn run(&self, input: &str, parts: &[u8]) -> Result<Report, Box<dyn Error>>;
+}
+
+impl<T: Solution> Run for T {
+    fn run(&self, input: &str, parts: &[u8]) -> Result<Report, Box<dyn Error>> {
+        let start = Instant::now();
+        let parsed = self.parse(input)?;
+        let parse = start.elapsed();
+
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                let start = Instant::now();
+                let answer = match part {
+                    1 => self.part1(&parsed),
+                    2 => self.part2(&parsed),
+                    _ => Err(format!("there's no part {}", part).into()),
+                };
+                Timed {
+                    part,
+                    answer,
+                    time: start.elapsed(),
+                }
+            })
+            .collect();
+        Ok(Report { parse, parts })
+    }
+}
+
+// inputs/dayNN.txt, looking in the current directory and the one above,
+// so it's found from the top of the repo or from inside a day's crate
+pub fn input_path(day: u8) -> PathBuf {
+    let name = Path::new("inputs").join(format!("day{:02}.txt", day));
+    let here = env::current_dir().unwrap_or_default();
+    here.ancestors()
+        .take(2)
+        .map(|x| x.join(&name))
+        .find(|x| x.exists())
+        .unwrap_or(name)
+}
+
+pub fn read_input(day: u8) -> io::Result<String> {
+    fs::read_to_string(input_path(day))
+}
+
+// what each day's own binary does: solves both parts of the day's input
+// and prints the answers, handing back the parsed input for anything else
+// the binary wants to do with it. if anything goes wrong it's reported
+// like the runner does, and the binary exits
+pub fn solve<T: Solution>(day: u8, solution: &T) -> T::Input {
+    let path = input_path(day);
+    let input = fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("can't read {}: {}", path.display(), e);
+        process::exit(1);
+    });
+    let parsed = solution.parse(&input).unwrap_or_else(|e| {
+        eprintln!("can't parse {}: {}", path.display(), e);
+        process::exit(1);
+    });
+    let mut failed = false;
+    for (part, answer) in [(1, solution.part1(&parsed)), (2, solution.part2(&parsed))] {
+        match answer {
+            Ok(answer) if answer.ends_with('\n') => print!("{}", answer),
+            Ok(answer) => println!("{}", answer),
+            Err(e) => {
+                eprintln!("part {}: error: {}", part, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+    parsed
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::{Answer, Solution};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i64>;
+
+        fn parse(&self, input: &str) -> Result<Vec<i64>, Box<dyn Error>> {
+            Ok(input
+                .split_whitespace()
+                .map(|x| x.parse())
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part1(&self, input: &Vec<i64>) -> Answer {
+            Ok(input.iter().sum::<i64>().to_string())
+        }
+
+        fn part2(&self, _: &Vec<i64>) -> Answer {
+            Err("not yet".into())
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        use super::Run;
+
+        let report = Sum.run("1 2 3", &[1, 2, 3]).unwrap();
+        let answers: Vec<String> = report
+            .parts
+            .iter()
+            .map(|x| match &x.answer {
+                Ok(answer) => answer.clone(),
+                Err(e) => e.to_string(),
+            })
+            .collect();
+        assert_eq!(answers, vec!["6", "not yet", "there's no part 3"]);
+        assert!(report.total() >= report.parse);
+        assert!(report.to_string().starts_with("parse ("));
+        assert!(report.to_string().contains("part 1: 6 ("));
+
+        assert!(Sum.run("1 x", &[1]).is_err());
+    }
+}
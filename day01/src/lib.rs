@@ -0,0 +1,47 @@
+use std::error::Error;
+
+use aoc::{Answer, Solution};
+
+pub fn fuel(module: isize) -> isize {
+    let mut total = 0;
+    let mut current = (module / 3) - 2;
+
+    while current > 0 {
+        total += current;
+        current = (current / 3) - 2;
+    }
+    total
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<isize>;
+
+    fn parse(&self, input: &str) -> Result<Vec<isize>, Box<dyn Error>> {
+        Ok(input
+            .lines()
+            .map(|x| x.trim().parse())
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(&self, input: &Vec<isize>) -> Answer {
+        Ok(input.iter().map(|x| (x / 3) - 2).sum::<isize>().to_string())
+    }
+
+    fn part2(&self, input: &Vec<isize>) -> Answer {
+        Ok(input.iter().map(|&x| fuel(x)).sum::<isize>().to_string())
+    }
+}
+
+mod test {
+
+    #[test]
+    fn test_fuel() {
+        use super::fuel;
+
+        assert_eq!(fuel(14), 2);
+        assert_eq!(fuel(1969), 966);
+        assert_eq!(fuel(100756), 50346);
+    }
+}
@@ -0,0 +1,88 @@
+use std::error::Error;
+
+use aoc::{Answer, Solution};
+
+pub fn run_intcode(input: &[usize]) -> Vec<usize> {
+    let mut output = input.to_vec();
+    let mut inx: usize = 0;
+
+    while let Some(&op) = output.get(inx) {
+        if op == 99 {
+            // terminate opcode
+            break;
+        };
+
+        let &inp1 = output.get(inx + 1).unwrap();
+        let &inp2 = output.get(inx + 2).unwrap();
+        let &outp = output.get(inx + 3).unwrap();
+
+        output[outp] = match op {
+            1 => output.get(inp1).unwrap() + output.get(inp2).unwrap(),
+            2 => output.get(inp1).unwrap() * output.get(inp2).unwrap(),
+            _ => panic!("got unexpected op code {}", op),
+        };
+
+        inx += 4;
+    }
+    output
+}
+
+pub fn mod_run(input: &[usize], noun: usize, verb: usize) -> usize {
+    let mut input = input.to_vec();
+    input[1] = noun;
+    input[2] = verb;
+    let output = run_intcode(&input);
+    *output.first().unwrap()
+}
+
+pub fn part2(input: &[usize], target: usize) -> usize {
+    for noun in 0..100 {
+        for verb in 0..100 {
+            if mod_run(input, noun, verb) == target {
+                return 100 * noun + verb;
+            }
+        }
+    }
+    panic!("a result wasn't found");
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<usize>;
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>, Box<dyn Error>> {
+        Ok(input
+            .trim_end()
+            .split(",")
+            .map(|x| x.parse())
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(&self, input: &Vec<usize>) -> Answer {
+        Ok(mod_run(input, 12, 2).to_string())
+    }
+
+    fn part2(&self, input: &Vec<usize>) -> Answer {
+        Ok(part2(input, 19690720).to_string())
+    }
+}
+
+mod test {
+
+    #[test]
+    fn test_run_intcode() {
+        use super::run_intcode;
+
+        assert_eq!(run_intcode(&[1, 0, 0, 0, 99]), vec![2, 0, 0, 0, 99]);
+        assert_eq!(run_intcode(&[2, 3, 0, 3, 99]), vec![2, 3, 0, 6, 99]);
+        assert_eq!(
+            run_intcode(&[2, 4, 4, 5, 99, 0]),
+            vec![2, 4, 4, 5, 99, 9801]
+        );
+        assert_eq!(
+            run_intcode(&[1, 1, 1, 4, 99, 5, 6, 0, 99]),
+            vec![30, 1, 1, 4, 2, 5, 6, 0, 99]
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::wire::{Point, Segment, Wire};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Crossing {
+    pub point: Point,
+    // indices of the two wires which cross, lowest first
+    pub wires: (usize, usize),
+    // manhattan distance from the origin
+    pub distance: usize,
+    // combined steps along both wires to reach the crossing
+    pub steps: usize,
+}
+
+// inserts sort before queries, which sort before removals, so that
+// segments which only touch at their end points are still found
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Event {
+    Insert(usize),
+    Query(usize),
+    Remove(usize),
+}
+
+struct Crossings {
+    // (point, wire a, wire b) -> (steps along a, steps along b)
+    found: HashMap<(Point, usize, usize), (usize, usize)>,
+}
+
+impl Crossings {
+    fn new() -> Self {
+        Crossings {
+            found: HashMap::new(),
+        }
+    }
+
+    fn record(&mut self, point: Point, a: (usize, &Segment), b: (usize, &Segment)) {
+        if a.0 == b.0 || point == Point(0, 0) {
+            return;
+        }
+        let (a, b) = if a.0 < b.0 { (a, b) } else { (b, a) };
+        let steps = (a.1.steps_to(&point), b.1.steps_to(&point));
+
+        // a wire may pass through the same point more than once,
+        // in which case we only care about the first visit
+        let entry = self.found.entry((point, a.0, b.0)).or_insert(steps);
+        entry.0 = entry.0.min(steps.0);
+        entry.1 = entry.1.min(steps.1);
+    }
+
+    fn into_vec(self) -> Vec<Crossing> {
+        let mut crossings: Vec<Crossing> = self
+            .found
+            .into_iter()
+            .map(|((point, a, b), (steps_a, steps_b))| Crossing {
+                point,
+                wires: (a, b),
+                distance: point.manhattan(Point::origin()) as usize,
+                steps: steps_a + steps_b,
+            })
+            .collect();
+        crossings.sort_by_key(|c| (c.distance, c.steps, c.point, c.wires));
+        crossings
+    }
+}
+
+// perpendicular crossings, found by sweeping a vertical line along x,
+// keeping the horizontal segments it currently intersects ordered by y
+fn sweep(segments: &[(usize, Segment)], crossings: &mut Crossings) {
+    let mut events = Vec::with_capacity(segments.len() * 2);
+    for (i, (_, segment)) in segments.iter().enumerate() {
+        if segment.is_horizontal() {
+            events.push((segment.min().0, Event::Insert(i)));
+            events.push((segment.max().0, Event::Remove(i)));
+        } else {
+            events.push((segment.start.0, Event::Query(i)));
+        }
+    }
+    events.sort();
+
+    let mut active: BTreeMap<isize, Vec<usize>> = BTreeMap::new();
+
+    for (x, event) in events {
+        match event {
+            Event::Insert(i) => active.entry(segments[i].1.start.1).or_default().push(i),
+            Event::Remove(i) => {
+                let y = segments[i].1.start.1;
+                let row = active.get_mut(&y).unwrap();
+                row.retain(|&j| j != i);
+                if row.is_empty() {
+                    active.remove(&y);
+                }
+            }
+            Event::Query(i) => {
+                let (wire, vertical) = &segments[i];
+                for (&y, row) in active.range(vertical.min().1..=vertical.max().1) {
+                    for &j in row {
+                        let (other, horizontal) = &segments[j];
+                        crossings.record(Point(x, y), (*wire, vertical), (*other, horizontal));
+                    }
+                }
+            }
+        }
+    }
+}
+
+// parallel segments lying on the same line cross at every point they share
+fn overlaps(segments: &[(usize, Segment)], crossings: &mut Crossings) {
+    let mut lines: HashMap<(bool, isize), Vec<usize>> = HashMap::new();
+    for (i, (_, segment)) in segments.iter().enumerate() {
+        let key = if segment.is_horizontal() {
+            (true, segment.start.1)
+        } else {
+            (false, segment.start.0)
+        };
+        lines.entry(key).or_default().push(i);
+    }
+
+    for ((horizontal, _), mut line) in lines {
+        // position along the line
+        let along = |p: Point| if horizontal { p.0 } else { p.1 };
+        line.sort_by_key(|&i| along(segments[i].1.min()));
+
+        for (n, &i) in line.iter().enumerate() {
+            let (wire, segment) = &segments[i];
+            let end = along(segment.max());
+
+            for &j in line[n + 1..].iter() {
+                let (other, next) = &segments[j];
+                if along(next.min()) > end {
+                    break;
+                }
+                if wire == other {
+                    continue;
+                }
+                for k in along(next.min())..=end.min(along(next.max())) {
+                    let point = if horizontal {
+                        Point(k, segment.start.1)
+                    } else {
+                        Point(segment.start.0, k)
+                    };
+                    crossings.record(point, (*wire, segment), (*other, next));
+                }
+            }
+        }
+    }
+}
+
+// every point at which two (or more) different wires cross, excluding
+// the origin, ordered by distance from the origin then by combined steps
+pub fn crossings(wires: &[Wire]) -> Vec<Crossing> {
+    let segments: Vec<(usize, Segment)> = wires
+        .iter()
+        .enumerate()
+        .flat_map(|(i, wire)| wire.segments().into_iter().map(move |s| (i, s)))
+        .collect();
+
+    let mut crossings = Crossings::new();
+    sweep(&segments, &mut crossings);
+    overlaps(&segments, &mut crossings);
+    crossings.into_vec()
+}
+
+pub fn closest_crossing(wires: &[Wire]) -> Option<usize> {
+    crossings(wires).iter().map(|c| c.distance).min()
+}
+
+pub fn closest_step_crossing(wires: &[Wire]) -> Option<usize> {
+    crossings(wires).iter().map(|c| c.steps).min()
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_crossings() {
+        use super::{crossings, Crossing};
+        use crate::wire::{parse_wires, Point};
+
+        let wires = parse_wires("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap();
+        assert_eq!(
+            crossings(&wires),
+            vec![
+                Crossing {
+                    point: Point(3, 3),
+                    wires: (0, 1),
+                    distance: 6,
+                    steps: 40
+                },
+                Crossing {
+                    point: Point(6, 5),
+                    wires: (0, 1),
+                    distance: 11,
+                    steps: 30
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overlapping_wires() {
+        use super::crossings;
+        use crate::wire::{parse_wires, Point};
+
+        let wires = parse_wires("U2,R5\nR1,U2,R2,D4").unwrap();
+        let points: Vec<Point> = crossings(&wires).iter().map(|c| c.point).collect();
+        assert_eq!(points, vec![Point(1, 2), Point(2, 2), Point(3, 2)]);
+    }
+
+    #[test]
+    fn test_three_wires() {
+        use super::crossings;
+        use crate::wire::parse_wires;
+
+        let wires = parse_wires("R8,U5,L5,D3\nU7,R6,D4,L4\nD1,R4,U10").unwrap();
+        let pairs: Vec<(usize, usize)> = crossings(&wires).iter().map(|c| c.wires).collect();
+        assert_eq!(pairs, vec![(0, 2), (0, 1), (1, 2), (0, 2), (1, 2), (0, 1)]);
+    }
+}
@@ -0,0 +1,74 @@
+use std::error::Error;
+
+use aoc::{Answer, Solution};
+
+pub mod crossing;
+pub mod render;
+pub mod wire;
+
+use crossing::{closest_crossing, closest_step_crossing};
+use wire::{parse_wires, Wire};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Wire>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Wire>, Box<dyn Error>> {
+        Ok(parse_wires(input)?)
+    }
+
+    fn part1(&self, wires: &Vec<Wire>) -> Answer {
+        let distance = closest_crossing(wires).ok_or("the wires don't cross")?;
+        Ok(distance.to_string())
+    }
+
+    fn part2(&self, wires: &Vec<Wire>) -> Answer {
+        let steps = closest_step_crossing(wires).ok_or("the wires don't cross")?;
+        Ok(steps.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_closest_crossing() {
+        use crate::crossing::closest_crossing;
+        use crate::wire::parse_wires;
+
+        let wires = parse_wires(
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72
+U62,R66,U55,R34,D71,R55,D58,R83",
+        )
+        .unwrap();
+        assert_eq!(closest_crossing(&wires), Some(159));
+
+        let wires = parse_wires(
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
+U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+        )
+        .unwrap();
+        assert_eq!(closest_crossing(&wires), Some(135));
+    }
+
+    #[test]
+    fn test_closest_step_crossing() {
+        use crate::crossing::closest_step_crossing;
+        use crate::wire::parse_wires;
+
+        let wires = parse_wires(
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72
+U62,R66,U55,R34,D71,R55,D58,R83",
+        )
+        .unwrap();
+        assert_eq!(closest_step_crossing(&wires), Some(610));
+
+        let wires = parse_wires(
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
+U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+        )
+        .unwrap();
+        assert_eq!(closest_step_crossing(&wires), Some(410));
+    }
+}
@@ -0,0 +1,235 @@
+use std::fmt::Write;
+
+use aoc_utils::svg::{colour, Frame};
+
+use crate::crossing::{crossings, Crossing};
+use crate::wire::{Point, Wire};
+
+const SVG_SIZE: f64 = 800.0;
+
+// bounding box of every wire, always including the origin
+struct Bounds {
+    min: Point,
+    max: Point,
+}
+
+impl Bounds {
+    fn of(wires: &[Wire]) -> Self {
+        let mut min = Point(0, 0);
+        let mut max = Point(0, 0);
+        for segment in wires.iter().flat_map(|w| w.segments()) {
+            for p in [segment.start, segment.end].iter() {
+                min = Point(min.0.min(p.0), min.1.min(p.1));
+                max = Point(max.0.max(p.0), max.1.max(p.1));
+            }
+        }
+        Bounds { min, max }
+    }
+
+    fn width(&self) -> isize {
+        self.max.0 - self.min.0
+    }
+
+    fn height(&self) -> isize {
+        self.max.1 - self.min.1
+    }
+}
+
+fn vertices(wire: &Wire) -> Vec<Point> {
+    let mut points = vec![Point(0, 0)];
+    points.extend(wire.segments().iter().map(|s| s.end));
+    points
+}
+
+pub fn svg(wires: &[Wire]) -> String {
+    let bounds = Bounds::of(wires);
+    let crossings = crossings(wires);
+
+    // markers and text are sized relative to the whole picture
+    let extent = bounds.width().max(bounds.height()).max(1) as f64;
+    let margin = extent / 20.0;
+    let radius = extent / 200.0;
+    let font = extent / 40.0;
+
+    // flipped, so 'U' points up
+    let frame = Frame::new(
+        (bounds.min.0 as f64, bounds.min.1 as f64),
+        (bounds.max.0 as f64, bounds.max.1 as f64),
+        (margin, margin),
+        (bounds.width() as f64, bounds.height() as f64),
+    );
+    let x = |p: &Point| frame.x(p.0 as f64);
+    let y = |p: &Point| frame.y(p.1 as f64);
+
+    let w = bounds.width() as f64 + 2.0 * margin;
+    let h = bounds.height() as f64 + 2.0 * margin;
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+        SVG_SIZE,
+        (SVG_SIZE * h / w).round(),
+        w,
+        h,
+    )
+    .unwrap();
+
+    for (i, wire) in wires.iter().enumerate() {
+        let points: Vec<String> = vertices(wire)
+            .iter()
+            .map(|p| format!("{},{}", x(p), y(p)))
+            .collect();
+        writeln!(
+            out,
+            r#"  <polyline points="{}" fill="none" stroke="{}" stroke-width="1" vector-effect="non-scaling-stroke"/>"#,
+            points.join(" "),
+            colour(i),
+        )
+        .unwrap();
+    }
+
+    for c in crossings.iter() {
+        writeln!(
+            out,
+            r#"  <circle cx="{}" cy="{}" r="{}" fill="black"/>"#,
+            x(&c.point),
+            y(&c.point),
+            radius,
+        )
+        .unwrap();
+    }
+
+    // crossings are ordered by distance, so the first is the closest
+    if let Some(Crossing {
+        point, distance, ..
+    }) = crossings.first()
+    {
+        writeln!(
+            out,
+            r#"  <circle cx="{cx}" cy="{cy}" r="{r}" fill="none" stroke="red" stroke-width="2" vector-effect="non-scaling-stroke"/>
+  <text x="{tx}" y="{cy}" font-size="{font}" fill="red">closest ({d})</text>"#,
+            cx = x(point),
+            cy = y(point),
+            r = radius * 4.0,
+            tx = x(point) + radius * 5.0,
+            font = font,
+            d = distance,
+        )
+        .unwrap();
+    }
+
+    let origin = Point(0, 0);
+    writeln!(
+        out,
+        r#"  <circle cx="{cx}" cy="{cy}" r="{r}" fill="green"/>
+  <text x="{tx}" y="{cy}" font-size="{font}" fill="green">origin</text>"#,
+        cx = x(&origin),
+        cy = y(&origin),
+        r = radius * 2.0,
+        tx = x(&origin) + radius * 3.0,
+        font = font,
+    )
+    .unwrap();
+
+    out.push_str("</svg>\n");
+    out
+}
+
+// a preview of the wires scaled down to fit within the given number
+// of characters; each wire is drawn with its index, '#' where wires
+// share a cell without crossing, 'X' for crossings, '*' for the
+// closest crossing and 'o' for the origin
+pub fn ascii(wires: &[Wire], width: usize, height: usize) -> String {
+    let bounds = Bounds::of(wires);
+    let scale = (bounds.width() as f64 / (width.max(2) - 1) as f64)
+        .max(bounds.height() as f64 / (height.max(2) - 1) as f64)
+        .max(1.0);
+
+    let cell = |p: &Point| {
+        (
+            ((p.0 - bounds.min.0) as f64 / scale).round() as usize,
+            ((bounds.max.1 - p.1) as f64 / scale).round() as usize,
+        )
+    };
+    let (columns, rows) = cell(&Point(bounds.max.0, bounds.min.1));
+    let mut grid = vec![vec![' '; columns + 1]; rows + 1];
+
+    for (i, wire) in wires.iter().enumerate() {
+        let mark = std::char::from_digit((i % 36) as u32, 36).unwrap();
+        for segment in wire.segments() {
+            let (start, end) = (cell(&segment.start), cell(&segment.end));
+            let columns = start.0.min(end.0)..=start.0.max(end.0);
+            for row in grid[start.1.min(end.1)..=start.1.max(end.1)].iter_mut() {
+                for c in row[columns.clone()].iter_mut() {
+                    *c = if *c == ' ' || *c == mark { mark } else { '#' };
+                }
+            }
+        }
+    }
+
+    let crossings = crossings(wires);
+    for c in crossings.iter() {
+        let (column, row) = cell(&c.point);
+        grid[row][column] = 'X';
+    }
+    if let Some(closest) = crossings.first() {
+        let (column, row) = cell(&closest.point);
+        grid[row][column] = '*';
+    }
+    let (column, row) = cell(&Point(0, 0));
+    grid[row][column] = 'o';
+
+    grid.iter()
+        .map(|row| row.iter().collect::<String>().trim_end().to_string() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_ascii() {
+        use super::ascii;
+        use crate::wire::parse_wires;
+
+        let wires = parse_wires("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap();
+        let expect = "\
+1111111
+1     1
+1  000X00
+1  0  1 0
+1 1*111 0
+1  0    0
+1       0
+o00000000
+";
+        assert_eq!(ascii(&wires, 80, 40), expect);
+    }
+
+    #[test]
+    fn test_ascii_scaled() {
+        use super::ascii;
+        use crate::wire::parse_wires;
+
+        let wires = parse_wires("R80,U50,L50,D30\nU70,R60,D40,L40").unwrap();
+        let preview = ascii(&wires, 9, 8);
+        assert_eq!(preview.lines().count(), 8);
+        assert!(preview.lines().all(|l| l.len() <= 9));
+    }
+
+    #[test]
+    fn test_svg() {
+        use super::svg;
+        use crate::wire::parse_wires;
+
+        let wires = parse_wires("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap();
+        let image = svg(&wires);
+        assert!(image.starts_with("<svg"));
+        assert_eq!(image.matches("<polyline").count(), 2);
+        assert!(image.contains("#1f77b4") && image.contains("#ff7f0e"));
+        assert!(image.contains("closest (6)"));
+        assert!(image.contains(">origin</text>"));
+        assert!(image.trim_end().ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,159 @@
+use std::str::FromStr;
+
+pub use aoc_utils::{Direction, Point};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Move {
+    pub direction: Direction,
+    pub length: usize,
+}
+
+impl FromStr for Move {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let direction = match s.chars().next() {
+            Some('U') => Direction::Up,
+            Some('D') => Direction::Down,
+            Some('L') => Direction::Left,
+            Some('R') => Direction::Right,
+            _ => return Err(format!("unexpected direction in move '{}'", s)),
+        };
+        let length = s[1..]
+            .parse()
+            .map_err(|_| format!("bad length in move '{}'", s))?;
+        Ok(Move { direction, length })
+    }
+}
+
+impl Move {
+    fn offset(&self) -> Point {
+        self.direction.unit() * self.length as isize
+    }
+}
+
+// a straight run of wire, along with the number of steps
+// taken along the wire to reach its start
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+    pub steps: usize,
+}
+
+impl Segment {
+    pub fn is_horizontal(&self) -> bool {
+        self.start.1 == self.end.1
+    }
+
+    pub fn min(&self) -> Point {
+        self.start.min(self.end)
+    }
+
+    pub fn max(&self) -> Point {
+        self.start.max(self.end)
+    }
+
+    // steps along the wire to reach a point on this segment
+    pub fn steps_to(&self, point: &Point) -> usize {
+        self.steps + self.start.manhattan(*point) as usize
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Wire {
+    pub moves: Vec<Move>,
+}
+
+impl FromStr for Wire {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let moves = s
+            .trim()
+            .split(',')
+            .map(|x| x.parse())
+            .collect::<Result<_, _>>()?;
+        Ok(Wire { moves })
+    }
+}
+
+impl Wire {
+    pub fn segments(&self) -> Vec<Segment> {
+        let mut segments = Vec::with_capacity(self.moves.len());
+        let mut current = Point(0, 0);
+        let mut steps = 0;
+
+        for m in self.moves.iter() {
+            let end = current + m.offset();
+            segments.push(Segment {
+                start: current,
+                end,
+                steps,
+            });
+            current = end;
+            steps += m.length;
+        }
+        segments
+    }
+}
+
+pub fn parse_wires(input: &str) -> Result<Vec<Wire>, String> {
+    input
+        .lines()
+        .filter(|x| !x.trim().is_empty())
+        .map(|x| x.parse())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_parse_move() {
+        use super::{Direction, Move};
+
+        assert_eq!(
+            "R75".parse::<Move>(),
+            Ok(Move {
+                direction: Direction::Right,
+                length: 75
+            })
+        );
+        assert!("X1".parse::<Move>().is_err());
+        assert!("U".parse::<Move>().is_err());
+    }
+
+    #[test]
+    fn test_segments() {
+        use super::{Point, Segment, Wire};
+
+        let wire: Wire = "R8,U5,L5,D3".parse().unwrap();
+        assert_eq!(
+            wire.segments(),
+            vec![
+                Segment {
+                    start: Point(0, 0),
+                    end: Point(8, 0),
+                    steps: 0
+                },
+                Segment {
+                    start: Point(8, 0),
+                    end: Point(8, 5),
+                    steps: 8
+                },
+                Segment {
+                    start: Point(8, 5),
+                    end: Point(3, 5),
+                    steps: 13
+                },
+                Segment {
+                    start: Point(3, 5),
+                    end: Point(3, 2),
+                    steps: 18
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::error::Error;
+use std::ops::Range;
+
+use aoc::{Answer, Solution};
+
+pub fn check_part1(code: usize) -> bool {
+    let mut value = code;
+    let mut current;
+    let mut previous = 10;
+    let mut has_double = false;
+
+    while value > 0 {
+        current = value % 10;
+        value /= 10;
+
+        // we're processing digits last to first
+        // so we have to reverse the criteria
+        // i.e. digits never increase
+        if current > previous {
+            return false;
+        }
+
+        if current == previous {
+            has_double = true;
+        }
+        previous = current;
+    }
+
+    has_double
+}
+
+pub fn check_part2(code: usize) -> bool {
+    let mut value = code;
+    let mut current;
+    let mut previous = 10;
+    let mut has_double = false;
+    let mut run_len = 1;
+
+    while value > 0 {
+        current = value % 10;
+        value /= 10;
+
+        // we're processing digits last to first
+        // so we have to reverse the criteria
+        // i.e. digits never increase
+        if current > previous {
+            return false;
+        }
+
+        if current == previous {
+            run_len += 1;
+        } else {
+            if run_len == 2 {
+                has_double = true;
+            }
+            run_len = 1;
+        }
+        previous = current;
+    }
+    // don't forget the boundry
+    if run_len == 2 {
+        has_double = true;
+    }
+
+    has_double
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Range<usize>;
+
+    // the range is given inclusive, e.g. 193651-649729
+    fn parse(&self, input: &str) -> Result<Range<usize>, Box<dyn Error>> {
+        let (start, end) = input
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| format!("expected a range, found {:?}", input.trim()))?;
+        Ok(start.parse()?..end.parse::<usize>()? + 1)
+    }
+
+    fn part1(&self, range: &Range<usize>) -> Answer {
+        Ok(range
+            .clone()
+            .filter(|&x| check_part1(x))
+            .count()
+            .to_string())
+    }
+
+    fn part2(&self, range: &Range<usize>) -> Answer {
+        Ok(range
+            .clone()
+            .filter(|&x| check_part2(x))
+            .count()
+            .to_string())
+    }
+}
+
+mod test {
+
+    #[test]
+    fn test_part1() {
+        use super::check_part1;
+
+        assert!(check_part1(111111));
+        assert!(!check_part1(223450));
+        assert!(!check_part1(123789));
+    }
+
+    #[test]
+    fn test_part2() {
+        use super::check_part2;
+
+        assert!(check_part2(112233));
+        assert!(!check_part2(123444));
+        assert!(check_part2(111122));
+    }
+}
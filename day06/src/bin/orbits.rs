@@ -0,0 +1,63 @@
+// answer questions about an orbit map without recompiling, e.g.
+//
+//   orbits inputs/day06.txt transfers YOU SAN
+//   orbits --json inputs/day06.txt < queries.txt
+//
+// with no query on the command line, queries are read one per line
+// from stdin until it closes (or `quit`)
+use std::env;
+use std::fs;
+use std::io::{self, BufRead};
+use std::process;
+
+use day06::query::{answer, Format};
+use day06::OrbitMap;
+
+const USAGE: &str = "usage: orbits [--json] MAP [QUERY...]
+
+queries:
+    depth X
+    path X Y
+    transfers X Y
+    ancestors X
+    children X
+    total-orbits";
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    let format = if args.first().map(|x| x.as_str()) == Some("--json") {
+        args.remove(0);
+        Format::Json
+    } else {
+        Format::Plain
+    };
+
+    if args.is_empty() || args[0] == "--help" {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    }
+
+    let input = fs::read_to_string(&args[0]).unwrap_or_else(|e| {
+        eprintln!("couldn't read {}: {}", args[0], e);
+        process::exit(1);
+    });
+    let orbits: OrbitMap = input.parse().unwrap_or_else(|e| {
+        eprintln!("couldn't load {}: {}", args[0], e);
+        process::exit(1);
+    });
+
+    if args.len() > 1 {
+        println!("{}", answer(&orbits, &args[1..].join(" "), format));
+        return;
+    }
+
+    for line in io::stdin().lock().lines() {
+        let line = line.unwrap();
+        match line.trim() {
+            "" => continue,
+            "quit" | "exit" => break,
+            query => println!("{}", answer(&orbits, query, format)),
+        }
+    }
+}
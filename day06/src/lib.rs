@@ -0,0 +1,477 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::{Answer, Solution};
+
+pub mod query;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrbitError {
+    // a line which isn't of the form `CENTRE)ORBITER`
+    Parse { line: usize, text: String },
+    MultipleParents { body: String, parents: Vec<String> },
+    Cycle(String),
+    UnknownBody(String),
+    NoParent(String),
+    Disconnected(String, String),
+}
+
+impl fmt::Display for OrbitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OrbitError::Parse { line, text } => {
+                write!(f, "line {}: expected CENTRE)ORBITER, got '{}'", line, text)
+            }
+            OrbitError::MultipleParents { body, parents } => {
+                write!(
+                    f,
+                    "{} orbits more than one body ({})",
+                    body,
+                    parents.join(", ")
+                )
+            }
+            OrbitError::Cycle(body) => write!(f, "{} is part of an orbit cycle", body),
+            OrbitError::UnknownBody(body) => write!(f, "unknown body {}", body),
+            OrbitError::NoParent(body) => write!(f, "{} doesn't orbit anything", body),
+            OrbitError::Disconnected(a, b) => write!(f, "{} and {} aren't connected", a, b),
+        }
+    }
+}
+
+impl Error for OrbitError {}
+
+// the tree of bodies, each stored by index and linked to whatever it orbits
+#[derive(Debug, Clone)]
+pub struct OrbitMap {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    depths: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl FromStr for OrbitMap {
+    type Err = OrbitError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut orbits = Vec::new();
+        for (n, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut parts = line.split(')');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(centre), Some(orbiter), None)
+                    if !centre.is_empty() && !orbiter.is_empty() =>
+                {
+                    orbits.push((centre, orbiter))
+                }
+                _ => {
+                    return Err(OrbitError::Parse {
+                        line: n + 1,
+                        text: line.to_string(),
+                    })
+                }
+            }
+        }
+        OrbitMap::new(&orbits)
+    }
+}
+
+impl OrbitMap {
+    pub fn new(orbits: &[(&str, &str)]) -> Result<Self, OrbitError> {
+        let mut map = OrbitMap {
+            names: Vec::new(),
+            ids: HashMap::new(),
+            parents: Vec::new(),
+            children: Vec::new(),
+            depths: Vec::new(),
+            sizes: Vec::new(),
+        };
+
+        for &(centre, orbiter) in orbits {
+            let centre = map.insert(centre);
+            let orbiter = map.insert(orbiter);
+            match map.parents[orbiter] {
+                Some(parent) if parent == centre => continue,
+                Some(parent) => {
+                    return Err(OrbitError::MultipleParents {
+                        body: map.names[orbiter].clone(),
+                        parents: vec![map.names[parent].clone(), map.names[centre].clone()],
+                    })
+                }
+                None => {
+                    map.parents[orbiter] = Some(centre);
+                    map.children[centre].push(orbiter);
+                }
+            }
+        }
+
+        map.depths = map.compute_depths()?;
+        map.sizes = map.compute_sizes();
+        Ok(map)
+    }
+
+    fn insert(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.parents.push(None);
+        self.children.push(Vec::new());
+        id
+    }
+
+    // walk up from each body until we reach one whose depth is already known
+    // (or a root), then fill in the depths on the way back down, so that
+    // every body is only visited once
+    fn compute_depths(&self) -> Result<Vec<usize>, OrbitError> {
+        let mut depths: Vec<Option<usize>> = vec![None; self.names.len()];
+        let mut on_stack = vec![false; self.names.len()];
+
+        for id in 0..self.names.len() {
+            let mut stack = Vec::new();
+            let mut current = id;
+            let mut depth = loop {
+                if let Some(depth) = depths[current] {
+                    break depth;
+                }
+                if on_stack[current] {
+                    return Err(OrbitError::Cycle(self.names[current].clone()));
+                }
+                on_stack[current] = true;
+                stack.push(current);
+                match self.parents[current] {
+                    Some(parent) => current = parent,
+                    None => {
+                        // the root itself is at depth zero
+                        stack.pop();
+                        depths[current] = Some(0);
+                        break 0;
+                    }
+                }
+            };
+            while let Some(body) = stack.pop() {
+                depth += 1;
+                depths[body] = Some(depth);
+            }
+        }
+        Ok(depths.into_iter().map(|d| d.unwrap()).collect())
+    }
+
+    // deepest bodies first, so every subtree is complete before its parent
+    fn compute_sizes(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.names.len()).collect();
+        order.sort_by_key(|&id| std::cmp::Reverse(self.depths[id]));
+
+        let mut sizes = vec![1; self.names.len()];
+        for id in order {
+            if let Some(parent) = self.parents[id] {
+                sizes[parent] += sizes[id];
+            }
+        }
+        sizes
+    }
+
+    fn id(&self, name: &str) -> Result<usize, OrbitError> {
+        self.ids
+            .get(name)
+            .copied()
+            .ok_or_else(|| OrbitError::UnknownBody(name.to_string()))
+    }
+
+    fn parent_id(&self, name: &str) -> Result<usize, OrbitError> {
+        self.parents[self.id(name)?].ok_or_else(|| OrbitError::NoParent(name.to_string()))
+    }
+
+    fn lca(&self, a: usize, b: usize) -> Option<usize> {
+        let (mut a, mut b) = (a, b);
+        while self.depths[a] > self.depths[b] {
+            a = self.parents[a].unwrap();
+        }
+        while self.depths[b] > self.depths[a] {
+            b = self.parents[b].unwrap();
+        }
+        while a != b {
+            a = self.parents[a]?;
+            b = self.parents[b]?;
+        }
+        Some(a)
+    }
+
+    fn common_id(&self, a: &str, b: &str) -> Result<(usize, usize, usize), OrbitError> {
+        let (ia, ib) = (self.id(a)?, self.id(b)?);
+        let common = self
+            .lca(ia, ib)
+            .ok_or_else(|| OrbitError::Disconnected(a.to_string(), b.to_string()))?;
+        Ok((ia, ib, common))
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn bodies(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(|x| x.as_str())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.ids.contains_key(name)
+    }
+
+    pub fn parent(&self, name: &str) -> Result<Option<&str>, OrbitError> {
+        Ok(self.parents[self.id(name)?].map(|p| self.names[p].as_str()))
+    }
+
+    pub fn children(&self, name: &str) -> Result<Vec<&str>, OrbitError> {
+        Ok(self.children[self.id(name)?]
+            .iter()
+            .map(|&c| self.names[c].as_str())
+            .collect())
+    }
+
+    // number of direct and indirect orbits of a body
+    pub fn depth(&self, name: &str) -> Result<usize, OrbitError> {
+        Ok(self.depths[self.id(name)?])
+    }
+
+    pub fn total_orbits(&self) -> usize {
+        self.depths.iter().sum()
+    }
+
+    // from the body's parent all the way up to its root
+    pub fn ancestors(&self, name: &str) -> Result<Vec<&str>, OrbitError> {
+        let mut ancestors = Vec::new();
+        let mut current = self.id(name)?;
+        while let Some(parent) = self.parents[current] {
+            ancestors.push(self.names[parent].as_str());
+            current = parent;
+        }
+        Ok(ancestors)
+    }
+
+    // the body itself plus everything which directly or indirectly orbits it
+    pub fn subtree_size(&self, name: &str) -> Result<usize, OrbitError> {
+        Ok(self.sizes[self.id(name)?])
+    }
+
+    pub fn common_ancestor(&self, a: &str, b: &str) -> Result<&str, OrbitError> {
+        let (_, _, common) = self.common_id(a, b)?;
+        Ok(&self.names[common])
+    }
+
+    // every body from a to b (inclusive), passing through their common ancestor
+    pub fn path(&self, a: &str, b: &str) -> Result<Vec<&str>, OrbitError> {
+        let (mut ia, mut ib, common) = self.common_id(a, b)?;
+
+        let mut path = vec![self.names[ia].as_str()];
+        while ia != common {
+            ia = self.parents[ia].unwrap();
+            path.push(&self.names[ia]);
+        }
+        let mut tail = Vec::new();
+        while ib != common {
+            tail.push(self.names[ib].as_str());
+            ib = self.parents[ib].unwrap();
+        }
+        path.extend(tail.into_iter().rev());
+        Ok(path)
+    }
+
+    // number of edges between two bodies
+    pub fn distance(&self, a: &str, b: &str) -> Result<usize, OrbitError> {
+        let (ia, ib, common) = self.common_id(a, b)?;
+        Ok(self.depths[ia] + self.depths[ib] - 2 * self.depths[common])
+    }
+
+    // orbital transfers needed to move from the body a is orbiting
+    // to the body b is orbiting
+    pub fn transfers(&self, a: &str, b: &str) -> Result<usize, OrbitError> {
+        let (pa, pb) = (self.parent_id(a)?, self.parent_id(b)?);
+        self.distance(&self.names[pa], &self.names[pb])
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph orbits {\n");
+        for (id, parent) in self.parents.iter().enumerate() {
+            match parent {
+                Some(parent) => out.push_str(&format!(
+                    "    \"{}\" -> \"{}\";\n",
+                    self.names[id], self.names[*parent]
+                )),
+                None if self.children[id].is_empty() => {
+                    out.push_str(&format!("    \"{}\";\n", self.names[id]))
+                }
+                None => {}
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = OrbitMap;
+
+    fn parse(&self, input: &str) -> Result<OrbitMap, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, orbits: &OrbitMap) -> Answer {
+        Ok(orbits.total_orbits().to_string())
+    }
+
+    fn part2(&self, orbits: &OrbitMap) -> Answer {
+        Ok(orbits.transfers("YOU", "SAN")?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE: &str = "COM)B
+B)C
+C)D
+D)E
+E)F
+B)G
+G)H
+D)I
+E)J
+J)K
+K)L
+K)YOU
+I)SAN";
+
+    #[test]
+    fn test_depth() {
+        use super::OrbitMap;
+
+        let orbits: OrbitMap = EXAMPLE.parse().unwrap();
+        assert_eq!(orbits.depth("COM"), Ok(0));
+        assert_eq!(orbits.depth("D"), Ok(3));
+        assert_eq!(orbits.depth("L"), Ok(7));
+        assert_eq!(orbits.total_orbits(), 42 + 7 + 5);
+    }
+
+    #[test]
+    fn test_relations() {
+        use super::OrbitMap;
+
+        let orbits: OrbitMap = EXAMPLE.parse().unwrap();
+        assert_eq!(orbits.ancestors("H"), Ok(vec!["G", "B", "COM"]));
+        assert_eq!(orbits.children("E"), Ok(vec!["F", "J"]));
+        assert_eq!(orbits.parent("COM"), Ok(None));
+        assert_eq!(orbits.subtree_size("COM"), Ok(14));
+        assert_eq!(orbits.subtree_size("E"), Ok(6));
+        assert_eq!(orbits.subtree_size("SAN"), Ok(1));
+    }
+
+    #[test]
+    fn test_common_ancestor() {
+        use super::OrbitMap;
+
+        let orbits: OrbitMap = EXAMPLE.parse().unwrap();
+        assert_eq!(orbits.common_ancestor("YOU", "SAN"), Ok("D"));
+        assert_eq!(orbits.common_ancestor("H", "F"), Ok("B"));
+        assert_eq!(orbits.common_ancestor("L", "E"), Ok("E"));
+        assert_eq!(
+            orbits.path("YOU", "SAN"),
+            Ok(vec!["YOU", "K", "J", "E", "D", "I", "SAN"])
+        );
+        assert_eq!(orbits.distance("YOU", "SAN"), Ok(6));
+    }
+
+    #[test]
+    fn test_transfers() {
+        use super::OrbitMap;
+
+        let orbits: OrbitMap = EXAMPLE.parse().unwrap();
+        assert_eq!(orbits.transfers("YOU", "SAN"), Ok(4));
+        assert_eq!(orbits.transfers("H", "C"), Ok(1));
+    }
+
+    #[test]
+    fn test_search() {
+        use super::OrbitMap;
+        use aoc_utils::bfs;
+
+        // walking the tree agrees with searching the orbits as a graph
+        let orbits: OrbitMap = EXAMPLE.parse().unwrap();
+        let neighbours = |x: &&str| {
+            let mut next = orbits.children(x).unwrap();
+            next.extend(orbits.parent(x).unwrap());
+            next
+        };
+        for a in orbits.bodies() {
+            let search = bfs(vec![a], neighbours, |_| false);
+            for b in orbits.bodies() {
+                assert_eq!(search.cost(&b), orbits.distance(a, b).ok());
+            }
+        }
+        let search = bfs(vec!["K"], neighbours, |&x| x == "I");
+        assert_eq!(search.cost(&"I"), orbits.transfers("YOU", "SAN").ok());
+        assert_eq!(search.path(&"I"), orbits.path("K", "I").ok());
+    }
+
+    #[test]
+    fn test_errors() {
+        use super::{OrbitError, OrbitMap};
+
+        assert_eq!(
+            "COM)A\nA)B\nC)B".parse::<OrbitMap>().unwrap_err(),
+            OrbitError::MultipleParents {
+                body: "B".to_string(),
+                parents: vec!["A".to_string(), "C".to_string()]
+            }
+        );
+        assert!(matches!(
+            "A)B\nB)C\nC)A".parse::<OrbitMap>(),
+            Err(OrbitError::Cycle(_))
+        ));
+        assert_eq!(
+            "COM)A\nA-B".parse::<OrbitMap>().unwrap_err(),
+            OrbitError::Parse {
+                line: 2,
+                text: "A-B".to_string()
+            }
+        );
+
+        let orbits: OrbitMap = "COM)A\nX)Y".parse().unwrap();
+        assert_eq!(
+            orbits.depth("Z"),
+            Err(OrbitError::UnknownBody("Z".to_string()))
+        );
+        assert_eq!(
+            orbits.transfers("COM", "A"),
+            Err(OrbitError::NoParent("COM".to_string()))
+        );
+        assert_eq!(
+            orbits.common_ancestor("A", "Y"),
+            Err(OrbitError::Disconnected("A".to_string(), "Y".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        use super::OrbitMap;
+
+        let orbits: OrbitMap = "COM)A\nA)B".parse().unwrap();
+        assert_eq!(
+            orbits.to_dot(),
+            "digraph orbits {\n    \"A\" -> \"COM\";\n    \"B\" -> \"A\";\n}\n"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::str::FromStr;
+
+use crate::{OrbitError, OrbitMap};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    Depth(String),
+    Path(String, String),
+    Transfers(String, String),
+    Ancestors(String),
+    Children(String),
+    TotalOrbits,
+}
+
+impl FromStr for Query {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let name = |i: usize| words[i].to_string();
+        match (words.first().copied(), words.len()) {
+            (Some("depth"), 2) => Ok(Query::Depth(name(1))),
+            (Some("path"), 3) => Ok(Query::Path(name(1), name(2))),
+            (Some("transfers"), 3) => Ok(Query::Transfers(name(1), name(2))),
+            (Some("ancestors"), 2) => Ok(Query::Ancestors(name(1))),
+            (Some("children"), 2) => Ok(Query::Children(name(1))),
+            (Some("total-orbits"), 1) => Ok(Query::TotalOrbits),
+            _ => Err(format!("unrecognised query '{}'", s.trim())),
+        }
+    }
+}
+
+impl Query {
+    fn name(&self) -> &'static str {
+        match self {
+            Query::Depth(_) => "depth",
+            Query::Path(_, _) => "path",
+            Query::Transfers(_, _) => "transfers",
+            Query::Ancestors(_) => "ancestors",
+            Query::Children(_) => "children",
+            Query::TotalOrbits => "total-orbits",
+        }
+    }
+
+    pub fn run(&self, orbits: &OrbitMap) -> Result<Answer, OrbitError> {
+        let bodies = |x: Vec<&str>| Answer::Bodies(x.into_iter().map(String::from).collect());
+        Ok(match self {
+            Query::Depth(x) => Answer::Count(orbits.depth(x)?),
+            Query::Path(a, b) => bodies(orbits.path(a, b)?),
+            Query::Transfers(a, b) => Answer::Count(orbits.transfers(a, b)?),
+            Query::Ancestors(x) => bodies(orbits.ancestors(x)?),
+            Query::Children(x) => bodies(orbits.children(x)?),
+            Query::TotalOrbits => Answer::Count(orbits.total_orbits()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Count(usize),
+    Bodies(Vec<String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Plain,
+    Json,
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// parse and run a single line of input, formatting either the answer
+// or whatever went wrong
+pub fn answer(orbits: &OrbitMap, line: &str, format: Format) -> String {
+    let query = line.parse::<Query>();
+    let result = query
+        .as_ref()
+        .map_err(|e| e.clone())
+        .and_then(|q| q.run(orbits).map_err(|e| e.to_string()));
+
+    match format {
+        Format::Plain => match result {
+            Ok(Answer::Count(n)) => n.to_string(),
+            Ok(Answer::Bodies(bodies)) => bodies.join(" "),
+            Err(e) => format!("error: {}", e),
+        },
+        Format::Json => {
+            let name = query.as_ref().map(|q| q.name()).unwrap_or("unknown");
+            let value = match result {
+                Ok(Answer::Count(n)) => format!("\"result\": {}", n),
+                Ok(Answer::Bodies(bodies)) => {
+                    let bodies: Vec<String> = bodies.iter().map(|x| json_string(x)).collect();
+                    format!("\"result\": [{}]", bodies.join(", "))
+                }
+                Err(e) => format!("\"error\": {}", json_string(&e)),
+            };
+            format!("{{\"query\": {}, {}}}", json_string(name), value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN";
+
+    #[test]
+    fn test_parse_query() {
+        use super::Query;
+
+        assert_eq!(
+            "transfers YOU SAN".parse(),
+            Ok(Query::Transfers("YOU".to_string(), "SAN".to_string()))
+        );
+        assert_eq!(" total-orbits ".parse(), Ok(Query::TotalOrbits));
+        assert!("depth".parse::<Query>().is_err());
+        assert!("orbit COM".parse::<Query>().is_err());
+    }
+
+    #[test]
+    fn test_answer_plain() {
+        use super::{answer, Format};
+        use crate::OrbitMap;
+
+        let orbits: OrbitMap = EXAMPLE.parse().unwrap();
+        assert_eq!(answer(&orbits, "depth L", Format::Plain), "7");
+        assert_eq!(answer(&orbits, "path H F", Format::Plain), "H G B C D E F");
+        assert_eq!(answer(&orbits, "transfers YOU SAN", Format::Plain), "4");
+        assert_eq!(answer(&orbits, "ancestors D", Format::Plain), "C B COM");
+        assert_eq!(answer(&orbits, "children B", Format::Plain), "C G");
+        assert_eq!(answer(&orbits, "total-orbits", Format::Plain), "54");
+        assert_eq!(
+            answer(&orbits, "depth X", Format::Plain),
+            "error: unknown body X"
+        );
+    }
+
+    #[test]
+    fn test_answer_json() {
+        use super::{answer, Format};
+        use crate::OrbitMap;
+
+        let orbits: OrbitMap = EXAMPLE.parse().unwrap();
+        assert_eq!(
+            answer(&orbits, "depth L", Format::Json),
+            r#"{"query": "depth", "result": 7}"#
+        );
+        assert_eq!(
+            answer(&orbits, "children E", Format::Json),
+            r#"{"query": "children", "result": ["F", "J"]}"#
+        );
+        assert_eq!(
+            answer(&orbits, "what", Format::Json),
+            r#"{"query": "unknown", "error": "unrecognised query 'what'"}"#
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::ops::Add;
+
+use aoc::{Answer, Solution};
+
+// the size of the password image
+pub const WIDTH: usize = 25;
+pub const HEIGHT: usize = 6;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Pixel {
+    Black,
+    White,
+    Transparent,
+}
+
+impl From<u32> for Pixel {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Pixel::Black,
+            1 => Pixel::White,
+            2 => Pixel::Transparent,
+            _ => panic!("unexpected pixel value {}", value),
+        }
+    }
+}
+
+impl Add for Pixel {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        match (self, other) {
+            (p, Pixel::Transparent) => p,
+            (_, p) => p,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct Layer {
+    pixels: Vec<Pixel>,
+}
+
+impl Add for Layer {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Layer {
+            pixels: self
+                .pixels
+                .iter()
+                .zip(other.pixels)
+                .map(|(&x, y)| x + y)
+                .collect(),
+        }
+    }
+}
+
+pub struct Image {
+    layers: Vec<Layer>,
+    width: u32,
+    height: u32,
+}
+
+impl Image {
+    pub fn from_raw(pixels: &[u32], width: u32, height: u32) -> Self {
+        let layer_size = (width * height) as usize;
+        let layers = pixels
+            .chunks(layer_size)
+            .map(|layer| Layer {
+                pixels: layer.iter().map(|&x| Pixel::from(x)).collect(),
+            })
+            .collect();
+
+        Image {
+            layers,
+            width,
+            height,
+        }
+    }
+
+    pub fn flatten(self) -> FlatImage {
+        // reverse because we want to merge bottom-up
+        let mut layers = self.layers.into_iter().rev();
+        let base = layers.next().unwrap();
+        let flat_layer = layers.fold(base, |acc, x| acc + x);
+
+        FlatImage {
+            pixels: flat_layer.pixels,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+pub struct FlatImage {
+    pixels: Vec<Pixel>,
+    width: u32,
+    #[allow(dead_code)]
+    height: u32,
+}
+
+impl fmt::Display for FlatImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pixels = &self.pixels[..];
+        for row in pixels.chunks(self.width as usize) {
+            writeln!(
+                f,
+                "{}",
+                row.iter()
+                    .map(|x| {
+                        match x {
+                            Pixel::Black => "█".to_string(),
+                            Pixel::White => " ".to_string(),
+                            Pixel::Transparent => panic!("got unexpecte transparent pixel"),
+                        }
+                    })
+                    .collect::<Vec<String>>()
+                    .join("")
+            )?
+        }
+        Ok(())
+    }
+}
+
+pub fn checksum(pixels: &[u32], width: usize, height: usize) -> u32 {
+    let layer_size = width * height;
+
+    let mut min0 = u32::MAX;
+    let mut result = 0;
+    let mut counter = HashMap::<u32, u32>::new();
+
+    for layer in pixels.chunks(layer_size) {
+        counter.clear();
+        for pixel in layer {
+            *counter.entry(*pixel).or_insert(0) += 1;
+        }
+        if *counter.get(&0).unwrap() < min0 {
+            min0 = *counter.get(&0).unwrap();
+            result = counter.get(&1).unwrap() * counter.get(&2).unwrap();
+        }
+    }
+
+    result
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+        input
+            .trim()
+            .chars()
+            .map(|x| {
+                x.to_digit(10)
+                    .filter(|&x| x <= 2)
+                    .ok_or_else(|| format!("unexpected pixel {:?}", x).into())
+            })
+            .collect()
+    }
+
+    fn part1(&self, pixels: &Vec<u32>) -> Answer {
+        Ok(checksum(pixels, WIDTH, HEIGHT).to_string())
+    }
+
+    fn part2(&self, pixels: &Vec<u32>) -> Answer {
+        let image = Image::from_raw(pixels, WIDTH as u32, HEIGHT as u32);
+        Ok(image.flatten().to_string())
+    }
+}
+
+mod test {
+    #[test]
+    fn test_add_pixels() {
+        use super::Pixel;
+
+        assert_eq!(Pixel::Black + Pixel::White, Pixel::White);
+        assert_eq!(Pixel::White + Pixel::Black, Pixel::Black);
+        assert_eq!(Pixel::Black + Pixel::Transparent, Pixel::Black);
+        assert_eq!(Pixel::White + Pixel::Transparent, Pixel::White);
+        assert_eq!(Pixel::Transparent + Pixel::Transparent, Pixel::Transparent);
+    }
+
+    #[test]
+    fn test_add_layer() {
+        use super::{Layer, Pixel};
+
+        let lower = Layer {
+            pixels: vec![
+                Pixel::Black,
+                Pixel::Transparent,
+                Pixel::Transparent,
+                Pixel::Transparent,
+            ],
+        };
+        let upper = Layer {
+            pixels: vec![
+                Pixel::White,
+                Pixel::White,
+                Pixel::Transparent,
+                Pixel::Transparent,
+            ],
+        };
+
+        assert_eq!(
+            lower + upper,
+            Layer {
+                pixels: vec![
+                    Pixel::White,
+                    Pixel::White,
+                    Pixel::Transparent,
+                    Pixel::Transparent
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_flatten() {
+        use super::{Image, Pixel};
+
+        let image = Image::from_raw(&vec![0, 2, 2, 2, 1, 1, 2, 2, 2, 2, 1, 2, 0, 0, 0, 0], 2, 2);
+        let image = image.flatten();
+
+        assert_eq!(
+            image.pixels,
+            vec![Pixel::Black, Pixel::White, Pixel::White, Pixel::Black]
+        );
+    }
+}
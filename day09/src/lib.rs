@@ -0,0 +1,40 @@
+use std::error::Error;
+
+use aoc::{Answer, Solution};
+use intcode::{Interupt, Program};
+
+pub fn run_program(mut program: Program, mode: isize) -> isize {
+    let mut value = 0;
+
+    let input = match program.execute() {
+        Interupt::Input(input) => input,
+        _ => panic!("unexpected interupt"),
+    };
+    program = input.input(mode);
+
+    while let Interupt::Output(output) = program.execute() {
+        let (prog, val) = output.receive();
+        program = prog;
+        value = val
+    }
+    value
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Program;
+
+    fn parse(&self, input: &str) -> Result<Program, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    // test mode, then sensor boost mode
+    fn part1(&self, program: &Program) -> Answer {
+        Ok(run_program(program.clone(), 1).to_string())
+    }
+
+    fn part2(&self, program: &Program) -> Answer {
+        Ok(run_program(program.clone(), 2).to_string())
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+
+use crate::{canonical, Angle, Point};
+
+// the laser starts pointing up and rotates clockwise, vaporising
+// the closest asteroid along each line of sight in turn
+pub struct Laser {
+    rotations: Vec<Vec<Point>>,
+}
+
+impl Laser {
+    pub fn new(origin: Point, points: &[Point]) -> Self {
+        // build a map of {canonical: [point]}, ordered by angle
+        let mut lines: BTreeMap<Angle, Vec<Point>> = BTreeMap::new();
+        for &point in points.iter().filter(|&&x| x != origin) {
+            lines
+                .entry(canonical(origin, point))
+                .or_default()
+                .push(point);
+        }
+        for line in lines.values_mut() {
+            line.sort_by_key(|&x| origin.manhattan(x));
+        }
+
+        // the nth closest point along each line is vaporised in the nth rotation
+        let mut rotations: Vec<Vec<Point>> = Vec::new();
+        for line in lines.values() {
+            for (rotation, &point) in line.iter().enumerate() {
+                if rotation == rotations.len() {
+                    rotations.push(Vec::new());
+                }
+                rotations[rotation].push(point);
+            }
+        }
+
+        Laser { rotations }
+    }
+
+    // every asteroid vaporised, grouped by the rotation it was vaporised in
+    pub fn rotations(&self) -> &[Vec<Point>] {
+        &self.rotations
+    }
+
+    pub fn schedule(&self) -> impl Iterator<Item = Point> + '_ {
+        self.rotations.iter().flatten().copied()
+    }
+
+    // the nth asteroid to be vaporised, counting from 1
+    pub fn nth(&self, n: usize) -> Option<Point> {
+        self.schedule().nth(n.checked_sub(1)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_small_example() {
+        use super::Laser;
+        use crate::{parse, Point};
+
+        let input = ".#....#####...#..
+##...##.#####..##
+##...#...#.#####.
+..#.....#...###..
+..#.#.....#....##";
+        let laser = Laser::new(Point(8, 3), &parse(input).unwrap().points);
+        let first: Vec<Point> = laser.schedule().take(9).collect();
+        assert_eq!(
+            first,
+            vec![
+                Point(8, 1),
+                Point(9, 0),
+                Point(9, 1),
+                Point(10, 0),
+                Point(9, 2),
+                Point(11, 1),
+                Point(12, 1),
+                Point(11, 2),
+                Point(15, 1),
+            ]
+        );
+        assert_eq!(laser.rotations().len(), 3);
+        assert_eq!(laser.rotations()[2], vec![Point(14, 3)]);
+    }
+
+    #[test]
+    fn test_large_example() {
+        use super::Laser;
+        use crate::{find_optimal_position, parse, Point};
+
+        let input = ".#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##";
+        let points = parse(input).unwrap().points;
+        let (viewable, origin) = find_optimal_position(&points).unwrap();
+        assert_eq!((viewable, origin), (210, Point(11, 13)));
+
+        let laser = Laser::new(origin, &points);
+        assert_eq!(laser.nth(0), None);
+        assert_eq!(laser.nth(1), Some(Point(11, 12)));
+        assert_eq!(laser.nth(2), Some(Point(12, 1)));
+        assert_eq!(laser.nth(3), Some(Point(12, 2)));
+        assert_eq!(laser.nth(10), Some(Point(12, 8)));
+        assert_eq!(laser.nth(20), Some(Point(16, 0)));
+        assert_eq!(laser.nth(50), Some(Point(16, 9)));
+        assert_eq!(laser.nth(100), Some(Point(10, 16)));
+        assert_eq!(laser.nth(199), Some(Point(9, 6)));
+        assert_eq!(laser.nth(200), Some(Point(8, 2)));
+        assert_eq!(laser.nth(201), Some(Point(10, 9)));
+        assert_eq!(laser.nth(299), Some(Point(11, 1)));
+        assert_eq!(laser.nth(300), None);
+        assert_eq!(laser.rotations()[0].len(), 210);
+    }
+}
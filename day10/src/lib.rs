@@ -0,0 +1,210 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use aoc::{Answer, Solution};
+pub use aoc_utils::Point;
+
+pub mod laser;
+pub mod visibility;
+
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub struct Angle(pub isize, pub isize);
+
+impl Angle {
+    // 0 for straight up and anything to the right of it,
+    // 1 for straight down and anything to the left of it
+    fn half(&self) -> u8 {
+        if self.0 > 0 || (self.0 == 0 && self.1 < 0) {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+// angles are ordered clockwise starting from straight up (remembering
+// our y axis is flipped), compared exactly by half-plane then by the
+// sign of the cross product rather than via floating-point atan2
+impl Ord for Angle {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.half().cmp(&other.half()).then_with(|| {
+            let cross = self.0 * other.1 - self.1 * other.0;
+            0.cmp(&cross)
+        })
+    }
+}
+
+impl PartialOrd for Angle {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub fn gcd(m: isize, n: isize) -> isize {
+    // https://rosettacode.org/wiki/Greatest_common_divisor#Rust
+    let mut m = m;
+    let mut n = n;
+
+    while m != 0 {
+        let old_m = m;
+        m = n % m;
+        n = old_m;
+    }
+    n.abs()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub found: char,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unexpected char '{}' at line {}, column {}",
+            self.found, self.line, self.column
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub width: usize,
+    pub height: usize,
+    pub points: Vec<Point>,
+}
+
+pub fn parse(input: &str) -> Result<Field, ParseError> {
+    let mut points = Vec::new();
+    let mut width = 0;
+    let mut height = 0;
+
+    for (y, line) in input.lines().enumerate() {
+        for (x, char) in line.chars().enumerate() {
+            match char {
+                '#' => points.push(Point(x as isize, y as isize)),
+                '.' => (),
+                c => {
+                    return Err(ParseError {
+                        line: y + 1,
+                        column: x + 1,
+                        found: c,
+                    })
+                }
+            };
+        }
+        width = width.max(line.chars().count());
+        height = y + 1;
+    }
+    Ok(Field {
+        width,
+        height,
+        points,
+    })
+}
+
+pub fn canonical(source: Point, target: Point) -> Angle {
+    // get the position of a point relative to the source
+    // then calculate a 'canonical' form
+    // all points with the same canonical form lie on a line from source
+    let x = target.0 - source.0;
+    let y = target.1 - source.1;
+    let g = gcd(x, y);
+
+    Angle(x / g, y / g)
+}
+
+pub fn viewable_from(origin: Point, points: &[Point]) -> usize {
+    points
+        .iter()
+        .filter(|&x| x != &origin) // skip the point itself
+        .map(|&x| canonical(origin, x))
+        .collect::<HashSet<Angle>>()
+        .len()
+}
+
+// the asteroid which can see the most others, if there are any at all
+pub fn find_optimal_position(points: &[Point]) -> Option<(usize, Point)> {
+    visibility::visibility(points)
+        .into_iter()
+        .map(|(x, count)| (count, x))
+        .max()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Field;
+
+    fn parse(&self, input: &str) -> Result<Field, Box<dyn Error>> {
+        Ok(parse(input.trim())?)
+    }
+
+    fn part1(&self, field: &Field) -> Answer {
+        let (viewable, _) = find_optimal_position(&field.points).ok_or("there are no asteroids")?;
+        Ok(viewable.to_string())
+    }
+
+    // the 200th asteroid vaporised from the monitoring station
+    fn part2(&self, field: &Field) -> Answer {
+        let (_, optimal) = find_optimal_position(&field.points).ok_or("there are no asteroids")?;
+        let laser = laser::Laser::new(optimal, &field.points);
+        let point = laser
+            .nth(200)
+            .ok_or("fewer than 200 asteroids to vaporise")?;
+        Ok((100 * point.0 + point.1).to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_parse() {
+        use super::{parse, Field, ParseError, Point};
+
+        assert_eq!(
+            parse(".#.\n..#\n"),
+            Ok(Field {
+                width: 3,
+                height: 2,
+                points: vec![Point(1, 0), Point(2, 1)]
+            })
+        );
+        assert_eq!(
+            parse(".#.\n.X#"),
+            Err(ParseError {
+                line: 2,
+                column: 2,
+                found: 'X'
+            })
+        );
+    }
+
+    #[test]
+    fn test_find_optimal_position() {
+        use super::{find_optimal_position, parse, Day10, Point};
+        use aoc::Solution;
+
+        let input = ".#..#
+.....
+#####
+....#
+...##";
+        let field = parse(input).unwrap();
+        assert_eq!(find_optimal_position(&field.points), Some((8, Point(3, 4))));
+        assert_eq!(find_optimal_position(&[]), None);
+
+        // an empty field parses, but has nowhere to put the station
+        let empty = Day10.parse("...").unwrap();
+        assert!(Day10.part1(&empty).is_err());
+        assert!(Day10.part2(&empty).is_err());
+    }
+}
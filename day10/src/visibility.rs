@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::{canonical, gcd, Angle, Field, Point};
+
+// characters used to shade asteroids in the heatmap, least visible first
+const SHADES: &[char] = &['.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+// the number of asteroids visible from every asteroid
+//
+// every asteroid still has to look at every other one, so this is O(n)
+// per asteroid and O(n^2) overall, the same as calling viewable_from on
+// each in turn. it only saves half the work: each pair of asteroids is
+// looked at once, as the line of sight from a to b is the reverse of the
+// line from b to a, so both are recorded from the same gcd
+pub fn visibility(points: &[Point]) -> HashMap<Point, usize> {
+    let mut lines: Vec<HashSet<Angle>> = vec![HashSet::new(); points.len()];
+
+    for (i, &a) in points.iter().enumerate() {
+        for (j, &b) in points.iter().enumerate().skip(i + 1) {
+            let angle = canonical(a, b);
+            lines[i].insert(angle);
+            lines[j].insert(Angle(-angle.0, -angle.1));
+        }
+    }
+
+    points
+        .iter()
+        .zip(lines.iter())
+        .map(|(&point, angles)| (point, angles.len()))
+        .collect()
+}
+
+// the closest asteroid along each line of sight, clockwise from up
+pub fn visible_from(origin: Point, points: &[Point]) -> Vec<Point> {
+    let mut closest: BTreeMap<Angle, Point> = BTreeMap::new();
+    for &point in points.iter().filter(|&&x| x != origin) {
+        closest
+            .entry(canonical(origin, point))
+            .and_modify(|x| {
+                if origin.manhattan(point) < origin.manhattan(*x) {
+                    *x = point
+                }
+            })
+            .or_insert(point);
+    }
+    closest.into_values().collect()
+}
+
+// the asteroids sitting directly between from and to, closest to from first
+pub fn blockers(from: Point, to: Point, points: &[Point]) -> Vec<Point> {
+    if from == to {
+        return Vec::new();
+    }
+    let asteroids: HashSet<&Point> = points.iter().collect();
+    let Angle(dx, dy) = canonical(from, to);
+    let steps = gcd(to.0 - from.0, to.1 - from.1);
+
+    (1..steps)
+        .map(|k| Point(from.0 + k * dx, from.1 + k * dy))
+        .filter(|x| asteroids.contains(x))
+        .collect()
+}
+
+// the field with each asteroid shaded by how many others it can see,
+// relative to the best position, which is marked with an 'X'
+pub fn heatmap(field: &Field) -> String {
+    let counts = visibility(&field.points);
+    let best = counts.iter().map(|(&p, &c)| (c, p)).max();
+    let most = best.map(|(c, _)| c).unwrap_or(0).max(1);
+
+    let mut grid = vec![vec![' '; field.width]; field.height];
+    for (point, count) in counts.iter() {
+        let shade = (count * (SHADES.len() - 1) + most / 2) / most;
+        grid[point.1 as usize][point.0 as usize] = SHADES[shade];
+    }
+    if let Some((_, point)) = best {
+        grid[point.1 as usize][point.0 as usize] = 'X';
+    }
+
+    grid.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE: &str = ".#..#
+.....
+#####
+....#
+...##";
+
+    #[test]
+    fn test_visibility() {
+        use super::visibility;
+        use crate::{parse, viewable_from};
+
+        let points = parse(EXAMPLE).unwrap().points;
+        let counts = visibility(&points);
+        assert_eq!(counts.len(), points.len());
+        for &point in points.iter() {
+            assert_eq!(counts[&point], viewable_from(point, &points));
+        }
+    }
+
+    #[test]
+    fn test_visible_from() {
+        use super::visible_from;
+        use crate::{parse, Point};
+
+        let points = parse(EXAMPLE).unwrap().points;
+        assert_eq!(
+            visible_from(Point(1, 0), &points),
+            vec![
+                Point(4, 0),
+                Point(4, 2),
+                Point(3, 2),
+                Point(4, 4),
+                Point(2, 2),
+                Point(1, 2),
+                Point(0, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blockers() {
+        use super::blockers;
+        use crate::{parse, Point};
+
+        let points = parse(EXAMPLE).unwrap().points;
+        assert_eq!(
+            blockers(Point(3, 4), Point(1, 0), &points),
+            vec![Point(2, 2)]
+        );
+        assert_eq!(
+            blockers(Point(0, 2), Point(4, 2), &points),
+            vec![Point(1, 2), Point(2, 2), Point(3, 2)]
+        );
+        assert_eq!(blockers(Point(1, 0), Point(4, 0), &points), vec![]);
+    }
+
+    #[test]
+    fn test_heatmap() {
+        use super::heatmap;
+        use crate::parse;
+
+        let field = parse(EXAMPLE).unwrap();
+        assert_eq!(heatmap(&field), " %  %\n     \n#%%%*\n    %\n   X%\n");
+    }
+}
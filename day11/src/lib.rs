@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use aoc::{Answer, Solution};
+use aoc_utils::{Direction, Point};
+use intcode::{Computer, Program};
+
+type Layout = HashMap<Point, isize>;
+
+struct Bot {
+    position: Point,
+    heading: Direction,
+}
+
+impl Bot {
+    fn new() -> Self {
+        Bot {
+            position: Point::origin(),
+            heading: Direction::Up,
+        }
+    }
+
+    fn step(&mut self, turn: isize) -> Point {
+        self.heading = match turn {
+            0 => self.heading.turn_left(),
+            1 => self.heading.turn_right(),
+            t => panic!("Got unexpected turn {}", t),
+        };
+        self.position = self.position.step(self.heading);
+        self.position
+    }
+}
+
+fn build_layout(mut computer: Computer, input: isize) -> Layout {
+    let mut layout = Layout::new();
+    let mut bot = Bot::new();
+    let mut input = input;
+
+    loop {
+        if computer.input(input).is_err() {
+            // assume halt
+            break;
+        };
+        let colour = computer.output().unwrap();
+        let heading = computer.output().unwrap();
+
+        layout.insert(bot.position, colour);
+
+        let position = bot.step(heading);
+
+        input = *layout.get(&position).unwrap_or(&0);
+    }
+    layout
+}
+
+fn display_layout(layout: &Layout) -> String {
+    let mut display = String::new();
+
+    //find bounds
+    let minx = layout.keys().map(|&x| x.0).min().unwrap();
+    let miny = layout.keys().map(|&x| x.1).min().unwrap();
+    let maxx = layout.keys().map(|&x| x.0).max().unwrap();
+    let maxy = layout.keys().map(|&x| x.1).max().unwrap();
+
+    // reverse y because we're printing top-down
+    for y in (miny..=maxy).rev() {
+        let line: Vec<&str> = (minx..maxx)
+            .map(|x| match layout.get(&Point(x, y)).unwrap_or(&0) {
+                0 => "█",
+                1 => " ",
+                _ => panic!("got unexpected colour"),
+            })
+            .collect();
+        display.push_str(&line.join(""));
+        display.push('\n');
+    }
+    display
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Program;
+
+    fn parse(&self, input: &str) -> Result<Program, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    // the number of panels painted starting on black, then the registration
+    // identifier painted starting on white
+    fn part1(&self, program: &Program) -> Answer {
+        Ok(build_layout(Computer::new(program.clone()), 0)
+            .len()
+            .to_string())
+    }
+
+    fn part2(&self, program: &Program) -> Answer {
+        Ok(display_layout(&build_layout(
+            Computer::new(program.clone()),
+            1,
+        )))
+    }
+}
@@ -0,0 +1,78 @@
+use std::cmp::Ordering;
+
+// gravity is applied to each axis independently (which is what makes
+// the per-axis period trick work), so a rule only ever sees the
+// positions of every body along a single axis and returns the change
+// in velocity for each of them.
+//
+// finding the period relies on the moons coming back round to where they
+// started, which the puzzle's rule guarantees because each step can be
+// undone. a rule which can't be undone might never repeat, and then the
+// period is None once the step limit runs out or a body gets too far
+pub trait Gravity {
+    fn axis(&self, positions: &[isize]) -> Vec<isize>;
+}
+
+fn cmp(left: isize, right: isize) -> isize {
+    match left.cmp(&right) {
+        Ordering::Equal => 0,
+        Ordering::Less => 1,
+        Ordering::Greater => -1,
+    }
+}
+
+// each body is pulled one unit towards every other body, compared pairwise
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Pairwise;
+
+impl Gravity for Pairwise {
+    fn axis(&self, positions: &[isize]) -> Vec<isize> {
+        positions
+            .iter()
+            .map(|&x| positions.iter().map(|&y| cmp(x, y)).sum())
+            .collect()
+    }
+}
+
+// the same rule as Pairwise, but after sorting the positions the pull
+// on a body is just (number of bodies above) - (number of bodies below)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sorted;
+
+impl Gravity for Sorted {
+    fn axis(&self, positions: &[isize]) -> Vec<isize> {
+        let n = positions.len();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by_key(|&i| positions[i]);
+
+        let mut deltas = vec![0; n];
+        let mut start = 0;
+        while start < n {
+            // bodies sharing a position don't pull on each other
+            let mut end = start;
+            while end < n && positions[order[end]] == positions[order[start]] {
+                end += 1;
+            }
+            let delta = (n - end) as isize - start as isize;
+            for &i in order[start..end].iter() {
+                deltas[i] = delta;
+            }
+            start = end;
+        }
+        deltas
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_sorted_matches_pairwise() {
+        use super::{Gravity, Pairwise, Sorted};
+
+        let positions = [3, -1, 5, 3, 0, -7, 3, 12];
+        assert_eq!(Sorted.axis(&positions), Pairwise.axis(&positions));
+        assert_eq!(Sorted.axis(&positions), vec![-1, 5, -5, -1, 3, 7, -1, -7]);
+        assert_eq!(Sorted.axis(&[]), vec![]);
+    }
+}
@@ -0,0 +1,409 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::{Answer, Solution};
+
+pub mod gravity;
+pub mod parse;
+pub mod plot;
+pub mod trajectory;
+
+pub use gravity::{Gravity, Pairwise, Sorted};
+use parse::{Format, ParseError};
+
+fn gcd(m: u64, n: u64) -> u64 {
+    let mut m = m;
+    let mut n = n;
+
+    while m != 0 {
+        let old_m = m;
+        m = n % m;
+        n = old_m;
+    }
+    n
+}
+
+// None if the result doesn't fit in a u64
+fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+fn energy(vector: &[isize]) -> isize {
+    vector.iter().map(|x| x.abs()).sum()
+}
+
+// the name used for an axis when printing, x, y, z, w, then a4, a5, ...
+pub fn axis_name(axis: usize) -> String {
+    match "xyzw".chars().nth(axis) {
+        Some(c) => c.to_string(),
+        None => format!("a{}", axis),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Moon {
+    pub position: Vec<isize>,
+    pub velocity: Vec<isize>,
+}
+
+impl Moon {
+    pub fn new(position: Vec<isize>) -> Self {
+        let velocity = vec![0; position.len()];
+        Moon { position, velocity }
+    }
+
+    pub fn potential(&self) -> isize {
+        energy(&self.position)
+    }
+
+    pub fn kinetic(&self) -> isize {
+        energy(&self.velocity)
+    }
+
+    pub fn total_energy(&self) -> isize {
+        self.kinetic() * self.potential()
+    }
+}
+
+// the most steps an axis is simulated for before giving up on it coming
+// back round. the puzzle's axes repeat within a few hundred thousand
+const MAX_STEPS: u64 = 100_000_000;
+
+// the axes are independent, i.e. the x positions don't depend on the
+// y positions, etc, so each can be simulated on its own. None if the axis
+// doesn't come back to where it started within the steps, or if a body
+// goes further or faster than an isize can count
+fn axis_period<G: Gravity>(
+    gravity: &G,
+    positions: Vec<isize>,
+    velocities: Vec<isize>,
+    limit: u64,
+) -> Option<u64> {
+    let initial = (positions.clone(), velocities.clone());
+    let mut positions = positions;
+    let mut velocities = velocities;
+
+    // each step of the puzzle's gravity can be reversed, so the first
+    // state to repeat has to be the initial state - no need to track
+    // every state seen
+    for steps in 1..=limit {
+        for (v, g) in velocities.iter_mut().zip(gravity.axis(&positions)) {
+            *v = v.checked_add(g)?;
+        }
+        for (p, v) in positions.iter_mut().zip(velocities.iter()) {
+            *p = p.checked_add(*v)?;
+        }
+
+        if positions == initial.0 && velocities == initial.1 {
+            return Some(steps);
+        }
+    }
+    None
+}
+
+// any number of bodies, in any number of dimensions, moved about by
+// some rule of gravity (by default, the puzzle's rule)
+#[derive(Clone)]
+pub struct Moons<G = Sorted> {
+    moons: Vec<Moon>,
+    dimensions: usize,
+    gravity: G,
+}
+
+impl Moons<Sorted> {
+    pub fn new(moons: Vec<Moon>) -> Result<Self, ParseError> {
+        Moons::with_gravity(moons, Sorted)
+    }
+
+    pub fn parse(input: &str, format: Format) -> Result<Self, ParseError> {
+        Moons::new(parse::parse(input, format)?)
+    }
+}
+
+impl FromStr for Moons<Sorted> {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Moons::parse(input, Format::detect(input))
+    }
+}
+
+impl<G: Gravity> Moons<G> {
+    // every moon needs the same number of dimensions, and the errors
+    // give each moon's place in the list as its line
+    pub fn with_gravity(moons: Vec<Moon>, gravity: G) -> Result<Self, ParseError> {
+        let moons = parse::check_dimensions(
+            moons
+                .into_iter()
+                .enumerate()
+                .map(|(i, m)| (i + 1, m))
+                .collect(),
+        )?;
+        let dimensions = moons.first().map(|m| m.position.len()).unwrap_or(0);
+        Ok(Moons {
+            moons,
+            dimensions,
+            gravity,
+        })
+    }
+
+    pub fn moons(&self) -> &[Moon] {
+        &self.moons
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn positions(&self, axis: usize) -> Vec<isize> {
+        self.moons.iter().map(|m| m.position[axis]).collect()
+    }
+
+    fn velocities(&self, axis: usize) -> Vec<isize> {
+        self.moons.iter().map(|m| m.velocity[axis]).collect()
+    }
+
+    pub fn step(&mut self) {
+        for axis in 0..self.dimensions {
+            let gravity = self.gravity.axis(&self.positions(axis));
+            for (m, g) in self.moons.iter_mut().zip(gravity) {
+                m.velocity[axis] += g;
+                m.position[axis] += m.velocity[axis];
+            }
+        }
+    }
+
+    pub fn energy(&self) -> isize {
+        self.moons.iter().map(|m| m.total_energy()).sum()
+    }
+}
+
+impl<G: Gravity + Clone> Moons<G> {
+    // energy in the system after simulating the given number of steps
+    pub fn total_energy(&self, steps: usize) -> isize {
+        let mut moons = self.clone();
+        for _ in 0..steps {
+            moons.step();
+        }
+        moons.energy()
+    }
+
+    // steps until the moons first return to their initial state,
+    // which is the lowest common multiple of the period of each axis
+    // (or None if it's too big to count, or they never come back)
+    pub fn period(&self) -> Option<u64> {
+        self.period_within(MAX_STEPS)
+    }
+
+    // the period, giving up on any axis which takes more than the
+    // given steps to come back round
+    pub fn period_within(&self, limit: u64) -> Option<u64> {
+        (0..self.dimensions)
+            .map(|axis| {
+                axis_period(
+                    &self.gravity,
+                    self.positions(axis),
+                    self.velocities(axis),
+                    limit,
+                )
+            })
+            .try_fold(1, |period, axis| lcm(period, axis?))
+    }
+}
+
+impl<G> fmt::Debug for Moons<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for m in &self.moons {
+            let axes: Vec<String> = m
+                .position
+                .iter()
+                .enumerate()
+                .map(|(i, x)| format!("{}={}", axis_name(i), x))
+                .collect();
+            write!(f, "<{}> ", axes.join(", "))?
+        }
+        Ok(())
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Moons;
+
+    fn parse(&self, input: &str) -> Result<Moons, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, moons: &Moons) -> Answer {
+        Ok(moons.total_energy(1000).to_string())
+    }
+
+    fn part2(&self, moons: &Moons) -> Answer {
+        let period = moons.period().ok_or("the moons never repeat")?;
+        Ok(period.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE1: &str = "<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>";
+
+    const EXAMPLE2: &str = "<x=-8, y=-10, z=0>
+<x=5, y=5, z=10>
+<x=2, y=-7, z=3>
+<x=9, y=-8, z=-3>";
+
+    #[test]
+    fn test_total_energy() {
+        use super::Moons;
+
+        let moons: Moons = EXAMPLE1.parse().unwrap();
+        assert_eq!(moons.total_energy(10), 179);
+        let moons: Moons = EXAMPLE2.parse().unwrap();
+        assert_eq!(moons.total_energy(100), 1940);
+    }
+
+    #[test]
+    fn test_period() {
+        use super::Moons;
+
+        let moons: Moons = EXAMPLE1.parse().unwrap();
+        assert_eq!(moons.period(), Some(2772));
+        let moons: Moons = EXAMPLE2.parse().unwrap();
+        assert_eq!(moons.period(), Some(4686774924));
+    }
+
+    #[test]
+    fn test_step() {
+        use super::{Moon, Moons};
+
+        let mut moons: Moons = EXAMPLE1.parse().unwrap();
+        moons.step();
+        assert_eq!(
+            moons.moons()[0],
+            Moon {
+                position: vec![2, -1, 1],
+                velocity: vec![3, -1, -1]
+            }
+        );
+        assert_eq!(
+            format!("{:?}", moons),
+            "<x=2, y=-1, z=1> <x=3, y=-7, z=-4> <x=1, y=-7, z=5> <x=2, y=2, z=0> "
+        );
+    }
+
+    #[test]
+    fn test_pairwise_gravity() {
+        use super::{parse, Moons, Pairwise};
+
+        let moons = parse::parse(EXAMPLE1, parse::Format::Angle).unwrap();
+        let moons = Moons::with_gravity(moons, Pairwise).unwrap();
+        assert_eq!(moons.total_energy(10), 179);
+        assert_eq!(moons.period(), Some(2772));
+    }
+
+    #[test]
+    fn test_dimensions() {
+        use super::Moons;
+
+        // a single axis is just the x axis of example 1
+        let moons: Moons = "-1\n2\n4\n3".parse().unwrap();
+        assert_eq!(moons.dimensions(), 1);
+        assert_eq!(moons.period(), Some(18));
+
+        let moons: Moons = "x,y,z,w\n-1,0,2,-1\n2,-10,-7,2\n4,-8,8,4\n3,5,-1,3"
+            .parse()
+            .unwrap();
+        assert_eq!(moons.dimensions(), 4);
+        assert_eq!(moons.period(), Some(2772));
+    }
+
+    #[test]
+    fn test_custom_gravity() {
+        use super::{Gravity, Moon, Moons};
+
+        // no gravity at all, so the bodies drift apart forever
+        #[derive(Clone)]
+        struct Weightless;
+
+        impl Gravity for Weightless {
+            fn axis(&self, positions: &[isize]) -> Vec<isize> {
+                vec![0; positions.len()]
+            }
+        }
+
+        let mut moons = Moons::with_gravity(
+            vec![Moon {
+                position: vec![0, 0],
+                velocity: vec![1, -2],
+            }],
+            Weightless,
+        )
+        .unwrap();
+        moons.step();
+        moons.step();
+        assert_eq!(moons.moons()[0].position, vec![2, -4]);
+        assert_eq!(moons.energy(), 18);
+    }
+
+    #[test]
+    fn test_never_repeats() {
+        use super::{Gravity, Moon, Moons};
+
+        // every body's pushed the same way every step, so they speed off
+        #[derive(Clone)]
+        struct Wind(isize);
+
+        impl Gravity for Wind {
+            fn axis(&self, positions: &[isize]) -> Vec<isize> {
+                vec![self.0; positions.len()]
+            }
+        }
+
+        let moons = vec![Moon::new(vec![0, 1]), Moon::new(vec![2, 3])];
+        let breeze = Moons::with_gravity(moons.clone(), Wind(1)).unwrap();
+        assert_eq!(breeze.period_within(1000), None);
+        // until they go faster than an isize can count
+        let gale = Moons::with_gravity(moons, Wind(isize::MAX / 2)).unwrap();
+        assert_eq!(gale.period(), None);
+    }
+
+    #[test]
+    fn test_mismatched_dimensions() {
+        use super::{Moon, Moons};
+        use crate::parse::ParseError;
+
+        let moons = vec![Moon::new(vec![1, 2]), Moon::new(vec![3])];
+        assert_eq!(
+            Moons::new(moons).err(),
+            Some(ParseError {
+                line: 2,
+                message: "expected 2 axes, got 1".to_string()
+            })
+        );
+        let moon = Moon {
+            position: vec![1, 2],
+            velocity: vec![0],
+        };
+        assert!(Moons::new(vec![moon]).is_err());
+    }
+
+    #[test]
+    fn test_lcm() {
+        use super::lcm;
+
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, 2), None);
+        assert_eq!(lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+    }
+}
@@ -1,157 +1,56 @@
-extern crate regex;
+use std::env;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::process;
 
-use regex::Regex;
-use std::cmp::Ordering;
-use std::fmt;
-use std::fs;
-use std::ops::Add;
+use day12::plot::{energy_plot, orbit_plot};
+use day12::trajectory::{ExportFormat, Trajectory};
+use day12::Day12;
 
-fn cmp(left: isize, right: isize) -> isize {
-    match left.cmp(&right) {
-        Ordering::Equal => 0,
-        Ordering::Less => 1,
-        Ordering::Greater => -1,
-    }
-}
-
-#[derive(Clone, Copy)]
-struct Triple {
-    x: isize,
-    y: isize,
-    z: isize,
-}
-
-impl Triple {
-    fn energy(&self) -> isize {
-        self.x.abs() + self.y.abs() + self.z.abs()
-    }
-
-    fn gravity(&self, other: Self) -> Self {
-        Triple {
-            x: cmp(self.x, other.x),
-            y: cmp(self.y, other.y),
-            z: cmp(self.z, other.z),
-        }
-    }
-}
-
-impl Add for Triple {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Triple {
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
-        }
-    }
-}
-
-type P = Triple;
-type V = Triple;
-
-#[derive(Clone, Copy)]
-struct Moon {
-    position: P,
-    velocity: V,
-}
-
-impl Moon {
-    fn new(position: P) -> Self {
-        Moon {
-            position: position,
-            velocity: V { x: 0, y: 0, z: 0 },
-        }
-    }
-
-    fn step(&mut self, gravity: Triple) {
-        self.velocity = self.velocity + gravity;
-        self.position = self.position + self.velocity;
-    }
-
-    fn gravity(&self, moons: &Vec<Moon>) -> V {
-        moons
-            .iter()
-            .map(|x| self.position.gravity(x.position))
-            .fold(V { x: 0, y: 0, z: 0 }, |x, y| x + y)
-    }
-
-    fn potential(&self) -> isize {
-        self.position.energy()
-    }
-
-    fn kinetic(&self) -> isize {
-        self.velocity.energy()
-    }
-
-    fn total_energy(&self) -> isize {
-        self.kinetic() * self.potential()
-    }
-}
-
-struct Moons {
-    moons: Vec<Moon>
-}
-
-impl Moons {
-    fn new(moons: Vec<Moon>) -> Self {
-        Moons {
-            moons: moons
+fn main() {
+    let moons = aoc::solve(12, &Day12);
+
+    // optionally dump the simulation, e.g.
+    // `day12 --steps 500 --csv moons.csv --energy energy.svg --orbits orbits.svg`
+    let mut steps = 1000;
+    let mut outputs = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--steps" => steps = args.next().unwrap().parse().unwrap(),
+            "--csv" | "--json" | "--energy" | "--orbits" => {
+                outputs.push((arg, args.next().unwrap()))
+            }
+            _ => panic!("unexpected argument {}", arg),
         }
     }
-
-    fn step(&mut self) {
-        let gravities: Vec<V> = self.moons.iter().map(|m| m.gravity(&self.moons)).collect();
-
-        for (m, g) in self.moons.iter_mut().zip(gravities) {
-            m.step(g);
+    if outputs.is_empty() {
+        return;
+    }
+
+    let trajectory = Trajectory::simulate(&moons, steps);
+    for (kind, path) in outputs {
+        match kind.as_str() {
+            "--csv" => trajectory
+                .export(
+                    &mut BufWriter::new(File::create(path).unwrap()),
+                    ExportFormat::Csv,
+                )
+                .unwrap(),
+            "--json" => trajectory
+                .export(
+                    &mut BufWriter::new(File::create(path).unwrap()),
+                    ExportFormat::JsonLines,
+                )
+                .unwrap(),
+            "--energy" => fs::write(path, energy_plot(&trajectory)).unwrap(),
+            _ => match orbit_plot(&trajectory, (0, 1)) {
+                Ok(svg) => fs::write(path, svg).unwrap(),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }
+            },
         }
     }
-
-    fn total_energy(&self) -> isize {
-        self.moons.iter().map(|m| m.total_energy()).sum()
-    }
-}
-
-impl fmt::Debug for Moons {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for m in &self.moons {
-            write!(f, "<x={}, y={}, z={}> ", m.position.x, m.position.y, m.position.z)?
-        };
-        Ok(())
-    }
-}
-
-fn total_energy(moons: &mut Moons, iterations: usize) -> isize {
-    for _ in 0..iterations {
-        moons.step();
-    }
-    moons.total_energy()
-}
-
-fn main() {
-    let input = fs::read_to_string("/home/chris/advent_of_code/2019/inputs/day12.txt").unwrap();
-
-    let re = Regex::new(r"<x=(\-?\d+), y=(\-?\d+), z=(\-?\d+)>").unwrap();
-    let moons: Vec<Moon> = re
-        .captures_iter(&input)
-        .map(|c| {
-            Moon::new(P {
-                x: c[1].parse().unwrap(),
-                y: c[2].parse().unwrap(),
-                z: c[3].parse().unwrap(),
-            })
-        })
-        .collect();
-    let mut moons = Moons::new(moons);
-
-    //let part1 = total_energy(&mut moons, 1000);
-    //println!("{}", part1);
-
-    loop {
-        moons.step();
-        let kinetic: isize = moons.moons.iter().map(|m| m.kinetic()).sum();
-        let potential: isize = moons.moons.iter().map(|m| m.potential()).sum();
-        println!("{} {}", kinetic, potential);
-    }
 }
@@ -0,0 +1,196 @@
+use std::error::Error;
+use std::fmt;
+
+use regex::Regex;
+
+use crate::Moon;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    // `<x=-1, y=0, z=2>`, optionally followed by a second `<...>`
+    // giving the velocity, e.g. `pos=<x=2, y=1>, vel=<x=0, y=-1>`
+    Angle,
+    // `-1,0,2`, one column per axis, with an optional header row
+    Csv,
+}
+
+impl Format {
+    // angle brackets or comma separated, based on the first line
+    pub fn detect(input: &str) -> Self {
+        match input.lines().map(|x| x.trim()).find(|x| !x.is_empty()) {
+            Some(line) if line.contains('<') => Format::Angle,
+            _ => Format::Csv,
+        }
+    }
+}
+
+fn error(line: usize, message: String) -> ParseError {
+    ParseError { line, message }
+}
+
+fn parse_angle(input: &str) -> Result<Vec<Moon>, ParseError> {
+    let group = Regex::new(r"<([^>]*)>").unwrap();
+    let value = Regex::new(r"^\s*\w+\s*=\s*(-?\d+)\s*$").unwrap();
+
+    let mut moons = Vec::new();
+    for (n, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut vectors = Vec::new();
+        for g in group.captures_iter(line) {
+            let vector = g[1]
+                .split(',')
+                .map(|x| match value.captures(x) {
+                    Some(c) => c[1]
+                        .parse()
+                        .map_err(|_| error(n + 1, format!("'{}' is out of range", &c[1]))),
+                    None => Err(error(n + 1, format!("expected axis=value, got '{}'", x))),
+                })
+                .collect::<Result<Vec<isize>, _>>()?;
+            vectors.push(vector);
+        }
+
+        let moon = match vectors.len() {
+            1 => Moon::new(vectors.remove(0)),
+            2 => {
+                let velocity = vectors.remove(1);
+                let position = vectors.remove(0);
+                if position.len() != velocity.len() {
+                    return Err(error(n + 1, "velocity doesn't match position".into()));
+                }
+                Moon { position, velocity }
+            }
+            _ => {
+                return Err(error(
+                    n + 1,
+                    format!("expected <x=.., ...>, got '{}'", line),
+                ))
+            }
+        };
+        moons.push((n + 1, moon));
+    }
+    check_dimensions(moons)
+}
+
+fn parse_csv(input: &str) -> Result<Vec<Moon>, ParseError> {
+    let mut moons = Vec::new();
+    for (n, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let values: Result<Vec<isize>, _> = line.split(',').map(|x| x.trim().parse()).collect();
+        match values {
+            Ok(position) => moons.push((n + 1, Moon::new(position))),
+            // the first row is allowed to name the axes
+            Err(_) if moons.is_empty() && n == 0 => continue,
+            Err(_) => return Err(error(n + 1, format!("expected integers, got '{}'", line))),
+        }
+    }
+    check_dimensions(moons)
+}
+
+pub(crate) fn check_dimensions(moons: Vec<(usize, Moon)>) -> Result<Vec<Moon>, ParseError> {
+    if let Some((_, first)) = moons.first() {
+        let dimensions = first.position.len();
+        for (line, moon) in moons.iter() {
+            if moon.position.len() != dimensions {
+                return Err(error(
+                    *line,
+                    format!("expected {} axes, got {}", dimensions, moon.position.len()),
+                ));
+            }
+            if moon.velocity.len() != dimensions {
+                return Err(error(*line, "velocity doesn't match position".into()));
+            }
+        }
+    }
+    Ok(moons.into_iter().map(|(_, moon)| moon).collect())
+}
+
+pub fn parse(input: &str, format: Format) -> Result<Vec<Moon>, ParseError> {
+    match format {
+        Format::Angle => parse_angle(input),
+        Format::Csv => parse_csv(input),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_parse_angle() {
+        use super::{parse, Format};
+        use crate::Moon;
+
+        let moons = parse("<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n", Format::Angle).unwrap();
+        assert_eq!(
+            moons,
+            vec![Moon::new(vec![-1, 0, 2]), Moon::new(vec![2, -10, -7])]
+        );
+
+        let moons = parse("pos=<x=2, y=1>, vel=<x=-3, y=0>", Format::Angle).unwrap();
+        assert_eq!(
+            moons,
+            vec![Moon {
+                position: vec![2, 1],
+                velocity: vec![-3, 0]
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        use super::{parse, Format};
+        use crate::Moon;
+
+        let moons = parse("x,y,z,w\n-1,0,2,5\n2, -10, -7, 0\n", Format::Csv).unwrap();
+        assert_eq!(
+            moons,
+            vec![Moon::new(vec![-1, 0, 2, 5]), Moon::new(vec![2, -10, -7, 0])]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use super::{parse, Format, ParseError};
+
+        assert_eq!(
+            parse("<x=1, y=2>\n<x=1, y=two>", Format::Angle),
+            Err(ParseError {
+                line: 2,
+                message: "expected axis=value, got ' y=two'".to_string()
+            })
+        );
+        assert_eq!(
+            parse("1,2,3\n4,5", Format::Csv),
+            Err(ParseError {
+                line: 2,
+                message: "expected 3 axes, got 2".to_string()
+            })
+        );
+        assert!(parse("1,2\nx,y", Format::Csv).is_err());
+    }
+
+    #[test]
+    fn test_detect() {
+        use super::Format;
+
+        assert_eq!(Format::detect("\n<x=1>"), Format::Angle);
+        assert_eq!(Format::detect("x,y\n1,2"), Format::Csv);
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt::Write;
+
+use aoc_utils::svg::{colour, Frame};
+
+use crate::axis_name;
+use crate::trajectory::Trajectory;
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 500.0;
+const MARGIN: f64 = 50.0;
+
+struct Series {
+    label: String,
+    points: Vec<(f64, f64)>,
+}
+
+// a bare bones line chart: the series are scaled to fill the plot area,
+// with the ranges printed along the axes and a legend in the corner
+fn line_chart(title: &str, x_label: &str, y_label: &str, series: &[Series]) -> String {
+    let all = series.iter().flat_map(|s| s.points.iter());
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (0.0, 0.0, 0.0, 0.0);
+    for (i, &(x, y)) in all.enumerate() {
+        if i == 0 {
+            min_x = x;
+            max_x = x;
+            min_y = y;
+            max_y = y;
+        }
+        min_x = f64::min(min_x, x);
+        max_x = f64::max(max_x, x);
+        min_y = f64::min(min_y, y);
+        max_y = f64::max(max_y, y);
+    }
+    let frame = Frame::new(
+        (min_x, min_y),
+        (max_x, max_y),
+        (MARGIN, MARGIN),
+        (WIDTH - 2.0 * MARGIN, HEIGHT - 2.0 * MARGIN),
+    );
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="12">
+  <rect width="{w}" height="{h}" fill="white"/>
+  <text x="{cx}" y="20" text-anchor="middle" font-size="16">{title}</text>
+  <line x1="{l}" y1="{b}" x2="{r}" y2="{b}" stroke="black"/>
+  <line x1="{l}" y1="{t}" x2="{l}" y2="{b}" stroke="black"/>
+  <text x="{cx}" y="{xl}" text-anchor="middle">{x_label}</text>
+  <text x="15" y="{cy}" text-anchor="middle" transform="rotate(-90 15 {cy})">{y_label}</text>
+  <text x="{l}" y="{xt}" text-anchor="start">{min_x}</text>
+  <text x="{r}" y="{xt}" text-anchor="end">{max_x}</text>
+  <text x="{yt}" y="{b}" text-anchor="end">{min_y}</text>
+  <text x="{yt}" y="{ytop}" text-anchor="end">{max_y}</text>"#,
+        w = WIDTH,
+        h = HEIGHT,
+        cx = WIDTH / 2.0,
+        cy = HEIGHT / 2.0,
+        l = MARGIN,
+        r = WIDTH - MARGIN,
+        t = MARGIN,
+        b = HEIGHT - MARGIN,
+        xl = HEIGHT - 10.0,
+        xt = HEIGHT - MARGIN + 15.0,
+        yt = MARGIN - 5.0,
+        ytop = MARGIN + 10.0,
+        title = title,
+        x_label = x_label,
+        y_label = y_label,
+        min_x = min_x,
+        max_x = max_x,
+        min_y = min_y,
+        max_y = max_y,
+    )
+    .unwrap();
+
+    for (i, s) in series.iter().enumerate() {
+        let colour = colour(i);
+        let points: Vec<String> = s
+            .points
+            .iter()
+            .map(|&(x, y)| format!("{:.2},{:.2}", frame.x(x), frame.y(y)))
+            .collect();
+        writeln!(
+            out,
+            r#"  <polyline points="{}" fill="none" stroke="{}" stroke-width="1"/>
+  <text x="{}" y="{}" fill="{}">{}</text>"#,
+            points.join(" "),
+            colour,
+            WIDTH - MARGIN + 5.0,
+            MARGIN + 15.0 * i as f64,
+            colour,
+            s.label,
+        )
+        .unwrap();
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+// potential, kinetic and total energy of the whole system over time
+pub fn energy_plot(trajectory: &Trajectory) -> String {
+    let energies = trajectory.energies();
+    let over_time = |f: &dyn Fn(&(isize, isize, isize)) -> isize| {
+        energies
+            .iter()
+            .enumerate()
+            .map(|(step, e)| (step as f64, f(e) as f64))
+            .collect()
+    };
+    let series = [
+        Series {
+            label: "potential".to_string(),
+            points: over_time(&|e| e.0),
+        },
+        Series {
+            label: "kinetic".to_string(),
+            points: over_time(&|e| e.1),
+        },
+        Series {
+            label: "total".to_string(),
+            points: over_time(&|e| e.2),
+        },
+    ];
+    line_chart("energy", "step", "energy", &series)
+}
+
+// the path of each moon, projected onto the plane of the two given axes
+pub fn orbit_plot(trajectory: &Trajectory, axes: (usize, usize)) -> Result<String, String> {
+    if axes.0 >= trajectory.dimensions() || axes.1 >= trajectory.dimensions() {
+        return Err(format!(
+            "can't project onto axes {:?} in {} dimensions",
+            axes,
+            trajectory.dimensions()
+        ));
+    }
+    let moons = trajectory.states().first().map(|m| m.len()).unwrap_or(0);
+    let series: Vec<Series> = (0..moons)
+        .map(|i| Series {
+            label: format!("moon {}", i),
+            points: trajectory
+                .states()
+                .iter()
+                .map(|m| (m[i].position[axes.0] as f64, m[i].position[axes.1] as f64))
+                .collect(),
+        })
+        .collect();
+    let (x, y) = (axis_name(axes.0), axis_name(axes.1));
+    Ok(line_chart(
+        &format!("orbits ({}, {})", x, y),
+        &x,
+        &y,
+        &series,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE: &str = "<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>";
+
+    #[test]
+    fn test_energy_plot() {
+        use super::energy_plot;
+        use crate::trajectory::Trajectory;
+        use crate::Moons;
+
+        let moons: Moons = EXAMPLE.parse().unwrap();
+        let svg = energy_plot(&Trajectory::simulate(&moons, 10));
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<polyline").count(), 3);
+        assert!(svg.contains(">kinetic</text>"));
+        // each line has a point per step, including the initial state
+        let line = svg.lines().find(|x| x.contains("<polyline")).unwrap();
+        assert_eq!(line.matches(',').count(), 11);
+    }
+
+    #[test]
+    fn test_orbit_plot() {
+        use super::orbit_plot;
+        use crate::trajectory::Trajectory;
+        use crate::Moons;
+
+        let moons: Moons = EXAMPLE.parse().unwrap();
+        let svg = orbit_plot(&Trajectory::simulate(&moons, 10), (0, 2)).unwrap();
+        assert_eq!(svg.matches("<polyline").count(), 4);
+        assert!(svg.contains(">orbits (x, z)</text>"));
+        assert!(svg.contains(">moon 3</text>"));
+
+        let line: Moons = "-1\n2\n4\n3".parse().unwrap();
+        assert_eq!(
+            orbit_plot(&Trajectory::simulate(&line, 10), (0, 1)),
+            Err("can't project onto axes (0, 1) in 1 dimensions".to_string())
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::io::{self, Write};
+
+use crate::{axis_name, Gravity, Moon, Moons};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    JsonLines,
+}
+
+// the state of every moon at each step of a simulation, starting
+// with the initial state at step 0
+#[derive(Debug, Clone)]
+pub struct Trajectory {
+    dimensions: usize,
+    states: Vec<Vec<Moon>>,
+}
+
+impl Trajectory {
+    pub fn simulate<G: Gravity + Clone>(moons: &Moons<G>, steps: usize) -> Self {
+        let mut moons = moons.clone();
+        let mut states = Vec::with_capacity(steps + 1);
+        states.push(moons.moons().to_vec());
+        for _ in 0..steps {
+            moons.step();
+            states.push(moons.moons().to_vec());
+        }
+        Trajectory {
+            dimensions: moons.dimensions(),
+            states,
+        }
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    pub fn states(&self) -> &[Vec<Moon>] {
+        &self.states
+    }
+
+    // (potential, kinetic, total) energy of the whole system at each step
+    pub fn energies(&self) -> Vec<(isize, isize, isize)> {
+        self.states
+            .iter()
+            .map(|moons| {
+                let potential = moons.iter().map(|m| m.potential()).sum();
+                let kinetic = moons.iter().map(|m| m.kinetic()).sum();
+                let total = moons.iter().map(|m| m.total_energy()).sum();
+                (potential, kinetic, total)
+            })
+            .collect()
+    }
+
+    // one row/line per moon per step
+    pub fn export<W: Write>(&self, out: &mut W, format: ExportFormat) -> io::Result<()> {
+        if format == ExportFormat::Csv {
+            let mut header = vec!["step".to_string(), "moon".to_string()];
+            header.extend((0..self.dimensions).map(axis_name));
+            header.extend((0..self.dimensions).map(|i| format!("v{}", axis_name(i))));
+            header.extend(vec!["potential".into(), "kinetic".into(), "total".into()]);
+            writeln!(out, "{}", header.join(","))?;
+        }
+
+        for (step, moons) in self.states.iter().enumerate() {
+            for (i, m) in moons.iter().enumerate() {
+                let join = |v: &[isize]| {
+                    v.iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                };
+                match format {
+                    ExportFormat::Csv => writeln!(
+                        out,
+                        "{},{},{},{},{},{},{}",
+                        step,
+                        i,
+                        join(&m.position),
+                        join(&m.velocity),
+                        m.potential(),
+                        m.kinetic(),
+                        m.total_energy()
+                    )?,
+                    ExportFormat::JsonLines => writeln!(
+                        out,
+                        r#"{{"step": {}, "moon": {}, "position": [{}], "velocity": [{}], "potential": {}, "kinetic": {}, "total": {}}}"#,
+                        step,
+                        i,
+                        join(&m.position),
+                        join(&m.velocity),
+                        m.potential(),
+                        m.kinetic(),
+                        m.total_energy()
+                    )?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE: &str = "<x=-1, y=0>\n<x=2, y=-10>";
+
+    #[test]
+    fn test_energies() {
+        use super::Trajectory;
+        use crate::Moons;
+
+        let moons: Moons = "<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>"
+            .parse()
+            .unwrap();
+        let trajectory = Trajectory::simulate(&moons, 10);
+        let energies = trajectory.energies();
+        assert_eq!(energies.len(), 11);
+        assert_eq!(energies[0], (51, 0, 0));
+        assert_eq!(energies[10].2, 179);
+    }
+
+    #[test]
+    fn test_export_csv() {
+        use super::{ExportFormat, Trajectory};
+        use crate::Moons;
+
+        let moons: Moons = EXAMPLE.parse().unwrap();
+        let mut out = Vec::new();
+        Trajectory::simulate(&moons, 1)
+            .export(&mut out, ExportFormat::Csv)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "step,moon,x,y,vx,vy,potential,kinetic,total
+0,0,-1,0,0,0,1,0,0
+0,1,2,-10,0,0,12,0,0
+1,0,0,-1,1,-1,1,2,2
+1,1,1,-9,-1,1,10,2,20
+"
+        );
+    }
+
+    #[test]
+    fn test_export_json_lines() {
+        use super::{ExportFormat, Trajectory};
+        use crate::Moons;
+
+        let moons: Moons = EXAMPLE.parse().unwrap();
+        let mut out = Vec::new();
+        Trajectory::simulate(&moons, 1)
+            .export(&mut out, ExportFormat::JsonLines)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.lines().count(), 4);
+        assert_eq!(
+            out.lines().last(),
+            Some(
+                r#"{"step": 1, "moon": 1, "position": [1,-9], "velocity": [-1,1], "potential": 10, "kinetic": 2, "total": 20}"#
+            )
+        );
+    }
+}
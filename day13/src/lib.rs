@@ -0,0 +1,174 @@
+extern crate intcode;
+
+use intcode::{Computer, Interupt, Program};
+use std::error::Error;
+use std::fmt;
+
+use aoc::{Answer, Solution};
+use aoc_utils::{Point, SparseGrid, YAxis};
+
+#[derive(Clone)]
+enum State {
+    NewGame(Program),
+    Continue(Interupt),
+    GameOver,
+}
+
+#[derive(Clone, PartialEq)]
+enum Tile {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+impl Tile {
+    fn new(id: usize) -> Self {
+        match id {
+            0 => Tile::Empty,
+            1 => Tile::Wall,
+            2 => Tile::Block,
+            3 => Tile::Paddle,
+            4 => Tile::Ball,
+            t => panic!("unknown tile {}", t),
+        }
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Tile::Empty => ' ',
+            Tile::Wall => '#',
+            Tile::Block => 'X',
+            Tile::Paddle => '=',
+            Tile::Ball => 'o',
+        }
+    }
+}
+
+struct Game {
+    program: State,
+    layout: SparseGrid<Tile>,
+    score: Option<usize>,
+    ballx: isize,
+    paddlex: isize,
+}
+
+impl Game {
+    fn new(program: Program) -> Self {
+        Game {
+            program: State::NewGame(program),
+            layout: SparseGrid::new(),
+            score: None,
+            ballx: 0,
+            paddlex: 0,
+        }
+    }
+
+    fn input(mut self, joystick: isize) -> Self {
+        if let State::Continue(Interupt::Input(input)) = self.program {
+            let program = input.input(joystick);
+            self.program = State::Continue(program.execute());
+        } else {
+            panic!("Can't input a non-input state");
+        };
+        self
+    }
+
+    fn refresh(mut self) -> Self {
+        if let State::GameOver = self.program {
+            return self;
+        }
+
+        //self.layout.clear();
+        let mut outputs = Vec::new();
+        loop {
+            let prog = match self.program {
+                State::GameOver => break,
+                State::NewGame(program) => State::Continue(program.execute()),
+                State::Continue(Interupt::Halt) => State::GameOver,
+                State::Continue(Interupt::Input(_)) => break,
+                State::Continue(Interupt::Output(output)) => {
+                    let (program, value) = output.receive();
+                    outputs.push(value);
+                    if outputs.len() == 3 {
+                        let pos = Point(*outputs.first().unwrap(), *outputs.get(1).unwrap());
+                        let tile = *outputs.get(2).unwrap() as usize;
+                        outputs.clear();
+                        // the score's drawn off the left of the screen
+                        if pos == Point(-1, 0) {
+                            self.score = Some(tile);
+                        } else {
+                            let tile = Tile::new(tile);
+                            if tile == Tile::Ball {
+                                self.ballx = pos.0;
+                            }
+                            if tile == Tile::Paddle {
+                                self.paddlex = pos.0;
+                            }
+                            self.layout.insert(pos, tile);
+                        }
+                    }
+                    State::Continue(program.execute())
+                }
+            };
+            self.program = prog;
+        }
+        self
+    }
+
+    fn auto(mut self) -> usize {
+        loop {
+            self = self.refresh();
+            if let State::GameOver = self.program {
+                break;
+            }
+            let input = if self.ballx < self.paddlex {
+                -1
+            } else if self.ballx > self.paddlex {
+                1
+            } else {
+                0
+            };
+            self = self.input(input);
+        }
+        self.score.expect("Score wasn't found")
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Current score: {}", self.score.unwrap_or(0))?;
+        write!(
+            f,
+            "{}",
+            self.layout
+                .render(YAxis::Down, |_, x| x.map_or(' ', Tile::symbol))
+        )
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Program;
+
+    fn parse(&self, input: &str) -> Result<Program, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    // the number of block tiles drawn on the screen to start with
+    fn part1(&self, program: &Program) -> Answer {
+        let computer = Computer::new(program.clone());
+        let blocks = computer.skip(2).step_by(3).filter(|&x| x == 2).count();
+        Ok(blocks.to_string())
+    }
+
+    // the score once the last block's broken, with two quarters in
+    fn part2(&self, program: &Program) -> Answer {
+        let mut program = program.clone();
+        program.set(0, 2);
+        let game = Game::new(program);
+        Ok(game.auto().to_string())
+    }
+}
@@ -0,0 +1,451 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::{Answer, Solution};
+
+pub mod report;
+
+pub const ORE: &str = "ORE";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReactionError {
+    Parse { line: usize, text: String },
+    UnknownChemical(String),
+    DuplicateProducer(String),
+    Cycle(String),
+    Overflow,
+}
+
+impl fmt::Display for ReactionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReactionError::Parse { line, text } => {
+                write!(f, "line {}: can't parse reaction '{}'", line, text)
+            }
+            ReactionError::UnknownChemical(c) => write!(f, "nothing produces {}", c),
+            ReactionError::DuplicateProducer(c) => write!(f, "{} is produced more than once", c),
+            ReactionError::Cycle(c) => write!(f, "{} is part of a reaction cycle", c),
+            ReactionError::Overflow => write!(f, "too much ore to count"),
+        }
+    }
+}
+
+impl Error for ReactionError {}
+
+fn parse_quantity(s: &str) -> Option<(String, u64)> {
+    let mut parts = s.split_whitespace();
+    let count = parts.next()?.parse().ok()?;
+    let id = parts.next()?;
+    match parts.next() {
+        None => Some((id.to_owned(), count)),
+        Some(_) => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reaction {
+    pub inputs: Vec<(String, u64)>,
+    pub output: String,
+    pub amount: u64,
+}
+
+impl FromStr for Reaction {
+    type Err = ();
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut inout = line.trim().split(" => ");
+        let inputs = inout
+            .next()
+            .ok_or(())?
+            .split(", ")
+            .map(parse_quantity)
+            .collect::<Option<Vec<_>>>()
+            .ok_or(())?;
+        let (output, amount) = inout.next().and_then(parse_quantity).ok_or(())?;
+        if inout.next().is_some() || amount == 0 {
+            return Err(());
+        }
+        Ok(Reaction {
+            inputs,
+            output,
+            amount,
+        })
+    }
+}
+
+// the reactions, keyed by what they produce, along with an ordering in
+// which every chemical comes before anything used to produce it
+#[derive(Debug, Clone)]
+pub struct Reactions {
+    outputs: HashMap<String, Reaction>,
+    order: Vec<String>,
+}
+
+impl FromStr for Reactions {
+    type Err = ReactionError;
+
+    fn from_str(lines: &str) -> Result<Self, Self::Err> {
+        let mut reactions = Vec::new();
+        for (n, line) in lines.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let reaction = line.parse().map_err(|_| ReactionError::Parse {
+                line: n + 1,
+                text: line.trim().to_string(),
+            })?;
+            reactions.push(reaction);
+        }
+        Reactions::new(reactions)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Visiting,
+    Done,
+}
+
+impl Reactions {
+    pub fn new(reactions: Vec<Reaction>) -> Result<Self, ReactionError> {
+        let mut outputs = HashMap::new();
+        for reaction in reactions {
+            if reaction.output == ORE || outputs.contains_key(&reaction.output) {
+                return Err(ReactionError::DuplicateProducer(reaction.output));
+            }
+            outputs.insert(reaction.output.clone(), reaction);
+        }
+        for reaction in outputs.values() {
+            for (input, _) in reaction.inputs.iter() {
+                if input != ORE && !outputs.contains_key(input) {
+                    return Err(ReactionError::UnknownChemical(input.clone()));
+                }
+            }
+        }
+
+        let mut reactions = Reactions {
+            outputs,
+            order: Vec::new(),
+        };
+        reactions.order = reactions.topological_order()?;
+        Ok(reactions)
+    }
+
+    // depth first, so each chemical is added after all of its inputs,
+    // then reversed
+    fn topological_order(&self) -> Result<Vec<String>, ReactionError> {
+        let mut marks: HashMap<&str, Mark> = HashMap::new();
+        let mut order = Vec::new();
+
+        // sorted so the order doesn't depend on the hashmap
+        let mut chemicals: Vec<&str> = self.outputs.keys().map(|x| x.as_str()).collect();
+        chemicals.sort_unstable();
+
+        for chemical in chemicals {
+            // (chemical, index of the next input to visit)
+            let mut stack = vec![(chemical, 0)];
+            while let Some((current, next)) = stack.pop() {
+                if next == 0 {
+                    match marks.get(current) {
+                        Some(Mark::Done) => continue,
+                        Some(Mark::Visiting) => {
+                            return Err(ReactionError::Cycle(current.to_string()))
+                        }
+                        None => {
+                            marks.insert(current, Mark::Visiting);
+                        }
+                    }
+                }
+                let inputs = &self.outputs[current].inputs;
+                match inputs.get(next) {
+                    Some((input, _)) => {
+                        stack.push((current, next + 1));
+                        if input != ORE {
+                            stack.push((input, 0));
+                        }
+                    }
+                    None => {
+                        marks.insert(current, Mark::Done);
+                        order.push(current.to_string());
+                    }
+                }
+            }
+        }
+
+        order.reverse();
+        Ok(order)
+    }
+
+    pub fn get(&self, chemical: &str) -> Option<&Reaction> {
+        self.outputs.get(chemical)
+    }
+
+    // every chemical which can be produced, each before any of its inputs
+    pub fn order(&self) -> &[String] {
+        &self.order
+    }
+
+    fn check(&self, chemical: &str) -> Result<(), ReactionError> {
+        if chemical == ORE || self.outputs.contains_key(chemical) {
+            Ok(())
+        } else {
+            Err(ReactionError::UnknownChemical(chemical.to_string()))
+        }
+    }
+
+    // how many of each chemical are needed to make the given amount of
+    // a chemical, including the ORE; since everything which uses a chemical
+    // comes before it in the order, its total is known by the time it's reached
+    pub fn needed(&self, chemical: &str, amount: u64) -> Result<HashMap<&str, u64>, ReactionError> {
+        self.check(chemical)?;
+        let mut needed: HashMap<&str, u64> = HashMap::new();
+        needed.insert(self.key(chemical), amount);
+
+        for current in self.order.iter() {
+            let required = match needed.get(current.as_str()) {
+                Some(&x) if x > 0 => x,
+                _ => continue,
+            };
+            let reaction = &self.outputs[current];
+            let runs = required.div_ceil(reaction.amount);
+            for (input, count) in reaction.inputs.iter() {
+                let extra = runs.checked_mul(*count).ok_or(ReactionError::Overflow)?;
+                let total = needed.entry(input.as_str()).or_insert(0);
+                *total = total.checked_add(extra).ok_or(ReactionError::Overflow)?;
+            }
+        }
+        Ok(needed)
+    }
+
+    pub fn ore_for(&self, chemical: &str, amount: u64) -> Result<u64, ReactionError> {
+        Ok(*self.needed(chemical, amount)?.get(ORE).unwrap_or(&0))
+    }
+
+    // the most of a chemical which can be made from a budget of ORE,
+    // found by doubling until the budget is exceeded, then bisecting
+    pub fn max_output(&self, chemical: &str, ore_budget: u64) -> Result<u64, ReactionError> {
+        let fits = |amount| match self.ore_for(chemical, amount) {
+            Ok(ore) => Ok(ore <= ore_budget),
+            Err(ReactionError::Overflow) => Ok(false),
+            Err(e) => Err(e),
+        };
+
+        // fits(low) always holds, fits(high) never does
+        let mut low = 0;
+        let mut high = 1;
+        while fits(high)? {
+            low = high;
+            high = match high.checked_mul(2) {
+                Some(x) => x,
+                None if fits(u64::MAX)? => return Ok(u64::MAX),
+                None => u64::MAX,
+            };
+        }
+        while high - low > 1 {
+            let middle = low + (high - low) / 2;
+            if fits(middle)? {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        Ok(low)
+    }
+
+    // the key in self, so the map of needed chemicals can borrow from self
+    fn key(&self, chemical: &str) -> &str {
+        match self.outputs.get_key_value(chemical) {
+            Some((key, _)) => key,
+            None => ORE,
+        }
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Reactions;
+
+    fn parse(&self, input: &str) -> Result<Reactions, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, reactions: &Reactions) -> Answer {
+        Ok(reactions.ore_for("FUEL", 1)?.to_string())
+    }
+
+    // the most fuel a trillion ore makes
+    fn part2(&self, reactions: &Reactions) -> Answer {
+        Ok(reactions.max_output("FUEL", 1_000_000_000_000)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE3: &str = "157 ORE => 5 NZVS
+165 ORE => 6 DCFZ
+44 XJWVT, 5 KHKGT, 1 QDVJ, 29 NZVS, 9 GPVTF, 48 HKGWZ => 1 FUEL
+12 HKGWZ, 1 GPVTF, 8 PSHF => 9 QDVJ
+179 ORE => 7 PSHF
+177 ORE => 5 HKGWZ
+7 DCFZ, 7 PSHF => 2 XJWVT
+165 ORE => 2 GPVTF
+3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT";
+
+    #[test]
+    fn test_parse_reaction() {
+        use super::Reaction;
+
+        assert_eq!(
+            "7 A, 1 B => 1 C".parse(),
+            Ok(Reaction {
+                inputs: vec![("A".to_string(), 7), ("B".to_string(), 1)],
+                output: "C".to_string(),
+                amount: 1
+            })
+        );
+        assert!("7 A, 1 B -> 1 C".parse::<Reaction>().is_err());
+        assert!("7 A => C".parse::<Reaction>().is_err());
+    }
+
+    #[test]
+    fn test_order() {
+        use super::Reactions;
+
+        let reactions: Reactions = EXAMPLE3.parse().unwrap();
+        let order = reactions.order();
+        assert_eq!(order.len(), 9);
+        assert_eq!(order[0], "FUEL");
+        let position = |c: &str| order.iter().position(|x| x == c).unwrap();
+        for chemical in order.iter() {
+            for (input, _) in reactions.get(chemical).unwrap().inputs.iter() {
+                if input != "ORE" {
+                    assert!(position(chemical) < position(input));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ore_for() {
+        use super::Reactions;
+
+        let reactions: Reactions = EXAMPLE3.parse().unwrap();
+        assert_eq!(reactions.ore_for("FUEL", 1), Ok(13312));
+        assert_eq!(reactions.ore_for("NZVS", 5), Ok(157));
+        assert_eq!(reactions.ore_for("NZVS", 6), Ok(314));
+        assert_eq!(reactions.ore_for("ORE", 12), Ok(12));
+        assert_eq!(reactions.ore_for("FUEL", 0), Ok(0));
+    }
+
+    #[test]
+    fn test_max_output() {
+        use super::Reactions;
+
+        let example4 = "2 VPVL, 7 FWMGM, 2 CXFTF, 11 MNCFX => 1 STKFG
+17 NVRVD, 3 JNWZP => 8 VPVL
+53 STKFG, 6 MNCFX, 46 VJHF, 81 HVMC, 68 CXFTF, 25 GNMV => 1 FUEL
+22 VJHF, 37 MNCFX => 5 FWMGM
+139 ORE => 4 NVRVD
+144 ORE => 7 JNWZP
+5 MNCFX, 7 RFSQX, 2 FWMGM, 2 VPVL, 19 CXFTF => 3 HVMC
+5 VJHF, 7 MNCFX, 9 VPVL, 37 CXFTF => 6 GNMV
+145 ORE => 6 MNCFX
+1 NVRVD => 8 CXFTF
+1 VJHF, 6 MNCFX => 4 RFSQX
+176 ORE => 6 VJHF";
+
+        let example5 = "171 ORE => 8 CNZTR
+7 ZLQW, 3 BMBT, 9 XCVML, 26 XMNCP, 1 WPTQ, 2 MZWV, 1 RJRHP => 4 PLWSL
+114 ORE => 4 BHXH
+14 VRPVC => 6 BMBT
+6 BHXH, 18 KTJDG, 12 WPTQ, 7 PLWSL, 31 FHTLT, 37 ZDVW => 1 FUEL
+6 WPTQ, 2 BMBT, 8 ZLQW, 18 KTJDG, 1 XMNCP, 6 MZWV, 1 RJRHP => 6 FHTLT
+15 XDBXC, 2 LTCX, 1 VRPVC => 6 ZLQW
+13 WPTQ, 10 LTCX, 3 RJRHP, 14 XMNCP, 2 MZWV, 1 ZLQW => 1 ZDVW
+5 BMBT => 4 WPTQ
+189 ORE => 9 KTJDG
+1 MZWV, 17 XDBXC, 3 XCVML => 2 XMNCP
+12 VRPVC, 27 CNZTR => 2 XDBXC
+15 KTJDG, 12 BHXH => 5 XCVML
+3 BHXH, 2 VRPVC => 7 MZWV
+121 ORE => 7 VRPVC
+7 XCVML => 6 RJRHP
+5 BHXH, 4 VRPVC => 5 LTCX";
+
+        let trillion = 1_000_000_000_000;
+
+        let reactions: Reactions = EXAMPLE3.parse().unwrap();
+        assert_eq!(reactions.max_output("FUEL", trillion), Ok(82892753));
+
+        let reactions: Reactions = example4.parse().unwrap();
+        assert_eq!(reactions.ore_for("FUEL", 1), Ok(180697));
+        assert_eq!(reactions.max_output("FUEL", trillion), Ok(5586022));
+
+        let reactions: Reactions = example5.parse().unwrap();
+        assert_eq!(reactions.ore_for("FUEL", 1), Ok(2210736));
+        assert_eq!(reactions.max_output("FUEL", trillion), Ok(460664));
+    }
+
+    #[test]
+    fn test_max_output_edges() {
+        use super::{ReactionError, Reactions};
+
+        let reactions: Reactions = EXAMPLE3.parse().unwrap();
+        assert_eq!(reactions.max_output("FUEL", 13311), Ok(0));
+        assert_eq!(reactions.max_output("FUEL", 13312), Ok(1));
+        // any intermediate chemical works as well as FUEL
+        assert_eq!(reactions.max_output("NZVS", 157 * 3), Ok(15));
+        assert_eq!(reactions.max_output("ORE", 1234), Ok(1234));
+        assert_eq!(reactions.max_output("ORE", u64::MAX), Ok(u64::MAX));
+        assert_eq!(
+            reactions.max_output("WATER", 1),
+            Err(ReactionError::UnknownChemical("WATER".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        use super::{ReactionError, Reactions};
+
+        assert_eq!(
+            "1 ORE => 1 A\n1 B => 1 FUEL"
+                .parse::<Reactions>()
+                .unwrap_err(),
+            ReactionError::UnknownChemical("B".to_string())
+        );
+        assert_eq!(
+            "1 ORE => 1 A\n2 ORE => 1 A"
+                .parse::<Reactions>()
+                .unwrap_err(),
+            ReactionError::DuplicateProducer("A".to_string())
+        );
+        assert!(matches!(
+            "1 ORE, 1 B => 1 A\n1 A => 1 B\n1 A => 1 FUEL".parse::<Reactions>(),
+            Err(ReactionError::Cycle(_))
+        ));
+        assert_eq!(
+            "1 ORE => 1 A\n1 A = 1 FUEL"
+                .parse::<Reactions>()
+                .unwrap_err(),
+            ReactionError::Parse {
+                line: 2,
+                text: "1 A = 1 FUEL".to_string()
+            }
+        );
+
+        let reactions: Reactions = "1 ORE => 1 A\n1 A => 1 FUEL".parse().unwrap();
+        assert_eq!(
+            reactions.ore_for("B", 1),
+            Err(ReactionError::UnknownChemical("B".to_string()))
+        );
+        let reactions: Reactions = "1000 ORE => 1 FUEL".parse().unwrap();
+        assert_eq!(
+            reactions.ore_for("FUEL", u64::MAX / 10),
+            Err(ReactionError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{ReactionError, Reactions, ORE};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub chemical: String,
+    // how much is used, including the amount requested
+    pub needed: u64,
+    // how many times the reaction producing it fires
+    pub runs: u64,
+    pub produced: u64,
+    pub spare: u64,
+    // ORE which went into making it, with the cost of each input shared
+    // out over everything made from it, spares included
+    pub ore: f64,
+}
+
+// a bill of materials for making some amount of a chemical
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub chemical: String,
+    pub amount: u64,
+    pub ore: u64,
+    // in the same order as the reactions, i.e. each before its inputs
+    pub lines: Vec<Line>,
+}
+
+impl Report {
+    pub fn new(reactions: &Reactions, chemical: &str, amount: u64) -> Result<Self, ReactionError> {
+        let needed = reactions.needed(chemical, amount)?;
+
+        // inputs come after their products in the order, so going
+        // backwards the cost of every input is known before it's used
+        let mut ore_per_unit: HashMap<&str, f64> = HashMap::new();
+        ore_per_unit.insert(ORE, 1.0);
+        let mut lines = Vec::new();
+
+        for current in reactions.order().iter().rev() {
+            let need = match needed.get(current.as_str()) {
+                Some(&x) if x > 0 => x,
+                _ => continue,
+            };
+            let reaction = reactions.get(current).unwrap();
+            let runs = need.div_ceil(reaction.amount);
+            let produced = runs * reaction.amount;
+            let ore: f64 = reaction
+                .inputs
+                .iter()
+                .map(|(input, count)| (runs * count) as f64 * ore_per_unit[input.as_str()])
+                .sum();
+            ore_per_unit.insert(current, ore / produced as f64);
+
+            lines.push(Line {
+                chemical: current.clone(),
+                needed: need,
+                runs,
+                produced,
+                spare: produced - need,
+                ore,
+            });
+        }
+        lines.reverse();
+
+        Ok(Report {
+            chemical: chemical.to_string(),
+            amount,
+            ore: *needed.get(ORE).unwrap_or(&0),
+            lines,
+        })
+    }
+
+    pub fn get(&self, chemical: &str) -> Option<&Line> {
+        self.lines.iter().find(|x| x.chemical == chemical)
+    }
+
+    // the reaction graph, with each chemical labelled by how it was used
+    pub fn to_dot(&self, reactions: &Reactions) -> String {
+        let mut labels: HashMap<&str, String> = self
+            .lines
+            .iter()
+            .map(|x| {
+                (
+                    x.chemical.as_str(),
+                    format!(
+                        "{}\\nneeded {}, made {} ({} runs)\\nspare {}, ore {:.1}",
+                        x.chemical, x.needed, x.produced, x.runs, x.spare, x.ore
+                    ),
+                )
+            })
+            .collect();
+        labels.insert(ORE, format!("{}\\n{}", ORE, self.ore));
+        dot(reactions, &labels)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {} needs {} ORE",
+            self.amount, self.chemical, self.ore
+        )?;
+        writeln!(
+            f,
+            "{:<10} {:>12} {:>8} {:>12} {:>8} {:>16}",
+            "chemical", "needed", "runs", "produced", "spare", "ore"
+        )?;
+        for x in self.lines.iter() {
+            writeln!(
+                f,
+                "{:<10} {:>12} {:>8} {:>12} {:>8} {:>16.1}",
+                x.chemical, x.needed, x.runs, x.produced, x.spare, x.ore
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// edges run from each input to the chemical it's used to make,
+// labelled with the quantities in the reaction
+fn dot(reactions: &Reactions, labels: &HashMap<&str, String>) -> String {
+    let mut out = String::from("digraph reactions {\n    rankdir=LR;\n");
+    let mut chemicals: Vec<&str> = reactions.order().iter().map(|x| x.as_str()).collect();
+    chemicals.push(ORE);
+
+    for chemical in chemicals.iter() {
+        if let Some(label) = labels.get(chemical) {
+            out.push_str(&format!("    \"{}\" [label=\"{}\"];\n", chemical, label));
+        }
+    }
+    for chemical in reactions.order().iter() {
+        let reaction = reactions.get(chemical).unwrap();
+        for (input, count) in reaction.inputs.iter() {
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{} => {}\"];\n",
+                input, chemical, count, reaction.amount
+            ));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+impl Reactions {
+    pub fn report(&self, chemical: &str, amount: u64) -> Result<Report, ReactionError> {
+        Report::new(self, chemical, amount)
+    }
+
+    pub fn to_dot(&self) -> String {
+        dot(self, &HashMap::new())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE1: &str = "10 ORE => 10 A
+1 ORE => 1 B
+7 A, 1 B => 1 C
+7 A, 1 C => 1 D
+7 A, 1 D => 1 E
+7 A, 1 E => 1 FUEL";
+
+    #[test]
+    fn test_report() {
+        use crate::Reactions;
+
+        let reactions: Reactions = EXAMPLE1.parse().unwrap();
+        let report = reactions.report("FUEL", 1).unwrap();
+        assert_eq!(report.ore, 31);
+        assert_eq!(report.lines.len(), 6);
+        assert_eq!(report.lines[0].chemical, "FUEL");
+
+        let a = report.get("A").unwrap();
+        assert_eq!((a.needed, a.runs, a.produced, a.spare), (28, 3, 30, 2));
+        assert_eq!(a.ore, 30.0);
+
+        let b = report.get("B").unwrap();
+        assert_eq!((b.needed, b.runs, b.spare), (1, 1, 0));
+
+        // C uses 7 A (7 ORE) and a B (1 ORE), and so on up to the FUEL,
+        // which only misses out on the ORE that went into the spare A
+        assert_eq!(report.get("C").unwrap().ore, 8.0);
+        assert_eq!(report.get("E").unwrap().ore, 22.0);
+        assert_eq!(report.get("FUEL").unwrap().ore, 29.0);
+    }
+
+    #[test]
+    fn test_report_intermediate() {
+        use crate::Reactions;
+
+        let reactions: Reactions = EXAMPLE1.parse().unwrap();
+        let report = reactions.report("C", 2).unwrap();
+        assert_eq!(report.ore, 22);
+        assert_eq!(report.get("FUEL"), None);
+        assert_eq!(report.get("C").unwrap().runs, 2);
+        assert_eq!(report.get("A").unwrap().spare, 6);
+    }
+
+    #[test]
+    fn test_display() {
+        use crate::Reactions;
+
+        let reactions: Reactions = "3 ORE => 2 A\n3 A => 1 FUEL".parse().unwrap();
+        let report = reactions.report("FUEL", 1).unwrap();
+        assert_eq!(
+            report.to_string(),
+            "1 FUEL needs 6 ORE
+chemical         needed     runs     produced    spare              ore
+FUEL                  1        1            1        0              4.5
+A                     3        2            4        1              6.0
+"
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        use crate::Reactions;
+
+        let reactions: Reactions = "3 ORE => 2 A\n3 A => 1 FUEL".parse().unwrap();
+        assert_eq!(
+            reactions.to_dot(),
+            "digraph reactions {
+    rankdir=LR;
+    \"A\" -> \"FUEL\" [label=\"3 => 1\"];
+    \"ORE\" -> \"A\" [label=\"3 => 2\"];
+}
+"
+        );
+        let dot = reactions.report("FUEL", 1).unwrap().to_dot(&reactions);
+        assert!(dot.contains("\"A\" [label=\"A\\nneeded 3, made 4 (2 runs)\\nspare 1, ore 6.0\"];"));
+        assert!(dot.contains("\"ORE\" [label=\"ORE\\n6\"];"));
+    }
+}
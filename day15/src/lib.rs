@@ -0,0 +1,238 @@
+extern crate intcode;
+extern crate ncurses;
+
+use intcode::{Computer, Program};
+use std::error::Error;
+use std::fmt;
+
+use aoc::{Answer, Solution};
+use aoc_utils::{bfs, Direction, Point, Search, SparseGrid, YAxis};
+
+// the droid's movement commands: north, south, west and east
+fn command(direction: Direction) -> isize {
+    match direction {
+        Direction::Up => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        Direction::Right => 4,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Tile {
+    Wall,
+    Empty,
+    Oxygen,
+    Bot,
+    Unknown,
+    Start,
+}
+
+impl From<isize> for Tile {
+    fn from(status: isize) -> Tile {
+        match status {
+            0 => Tile::Wall,
+            1 => Tile::Empty,
+            2 => Tile::Oxygen,
+            t => panic!("unknown tile {}", t),
+        }
+    }
+}
+
+impl Tile {
+    fn symbol(&self) -> char {
+        match self {
+            Tile::Wall => '#',
+            Tile::Empty => '.',
+            Tile::Oxygen => 'X',
+            Tile::Bot => 'O',
+            Tile::Unknown => '?',
+            Tile::Start => '*',
+        }
+    }
+}
+
+struct Bot {
+    cpu: Computer,
+    position: Point,
+}
+
+impl Bot {
+    fn new(cpu: Computer) -> Self {
+        Bot {
+            cpu,
+            position: Point::origin(),
+        }
+    }
+
+    fn go(&mut self, direction: Direction) -> Tile {
+        self.cpu.input(command(direction)).unwrap();
+        let tile = self.cpu.output().unwrap().into();
+
+        self.position = match (tile, direction) {
+            (Tile::Wall, _) => self.position,
+            (_, direction) => self.position.step(direction),
+        };
+        tile
+    }
+}
+
+pub struct Area {
+    bot: Bot,
+    layout: SparseGrid<Tile>,
+}
+
+impl Area {
+    fn new(bot: Bot) -> Self {
+        let mut layout = SparseGrid::new();
+        layout.insert(Point::origin(), Tile::Start);
+        Area { bot, layout }
+    }
+
+    // tries every way out of every square the bot reaches, backing up the
+    // way it came when there's nowhere new to go
+    fn explore(&mut self) {
+        for &direction in Direction::ALL.iter() {
+            let target = self.bot.position.step(direction);
+            if self.layout.contains(target) {
+                continue;
+            }
+            let tile = self.bot.go(direction);
+            self.layout.insert(target, tile);
+            if tile != Tile::Wall {
+                self.explore();
+                self.bot.go(direction.reverse());
+            }
+        }
+    }
+
+    // the explored squares next to a square that aren't walls
+    fn open_adjacent(&self, p: Point) -> Vec<Point> {
+        self.layout
+            .neighbours(p)
+            .filter(|(_, &x)| x != Tile::Wall)
+            .map(|(x, _)| x)
+            .collect()
+    }
+
+    // the fewest moves from the start to the oxygen system
+    fn route(&self) -> Option<usize> {
+        let search = bfs(
+            vec![Point::origin()],
+            |&p| self.open_adjacent(p),
+            |&p| self.layout.get(p) == Some(&Tile::Oxygen),
+        );
+        search.cost(search.goal()?)
+    }
+
+    // a flood fill out from the oxygen system, once the area's explored
+    fn flood(&self) -> Option<Search<Point>> {
+        let (oxygen, _) = self.layout.iter().find(|(_, &x)| x == Tile::Oxygen)?;
+        Some(flood(oxygen, |p| self.open_adjacent(p)))
+    }
+}
+
+pub fn explore(program: Program) -> Area {
+    let mut area = Area::new(Bot::new(Computer::new(program)));
+    area.explore();
+    area
+}
+
+impl fmt::Display for Area {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let map = self.layout.render(YAxis::Up, |p, x| {
+            if p == Point::origin() {
+                Tile::Start.symbol()
+            } else if p == self.bot.position {
+                Tile::Bot.symbol()
+            } else {
+                x.unwrap_or(&Tile::Unknown).symbol()
+            }
+        });
+        write!(f, "{}", map)
+    }
+}
+
+pub fn run_interactive(program: Program) {
+    let mut area = Area::new(Bot::new(Computer::new(program)));
+    ncurses::initscr();
+    ncurses::keypad(ncurses::stdscr(), true);
+    ncurses::noecho();
+
+    loop {
+        ncurses::clear();
+
+        ncurses::addstr(&format!("{}", area));
+
+        let direction = match ncurses::getch() {
+            ncurses::constants::KEY_RIGHT => Direction::Right,
+            ncurses::constants::KEY_LEFT => Direction::Left,
+            ncurses::constants::KEY_UP => Direction::Up,
+            ncurses::constants::KEY_DOWN => Direction::Down,
+            _ => break,
+        };
+        let target = area.bot.position.step(direction);
+        let tile = area.bot.go(direction);
+        area.layout.insert(target, tile);
+
+        ncurses::refresh();
+    }
+
+    ncurses::endwin();
+}
+
+// the minutes the oxygen takes to reach every square it can get to, as
+// it spreads out from the start
+fn flood(start: Point, open_adjacent: impl Fn(Point) -> Vec<Point>) -> Search<Point> {
+    bfs(vec![start], |&p| open_adjacent(p), |_| false)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Program;
+
+    fn parse(&self, input: &str) -> Result<Program, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    // the fewest moves from the start to the oxygen system
+    fn part1(&self, program: &Program) -> Answer {
+        let moves = explore(program.clone())
+            .route()
+            .ok_or("didn't find the oxygen system")?;
+        Ok(moves.to_string())
+    }
+
+    // the minutes the oxygen takes to fill the area
+    fn part2(&self, program: &Program) -> Answer {
+        let flood = explore(program.clone())
+            .flood()
+            .ok_or("didn't find the oxygen system")?;
+        Ok(flood.costs().values().max().unwrap().to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_flood_fill() {
+        use super::flood;
+        use aoc_utils::{Point, SparseGrid, YAxis};
+
+        let map = " ##
+#..##
+#.#..#
+#.X.#
+ ###";
+        let open = SparseGrid::parse(map, YAxis::Down, |c| match c {
+            '.' | 'X' => Some(c),
+            _ => None,
+        });
+        let open_adjacent = |p: Point| open.neighbours(p).map(|(x, _)| x).collect();
+        let flood = flood(Point(2, 3), open_adjacent);
+        assert_eq!(flood.costs().len(), open.len());
+        assert_eq!(flood.costs().values().max(), Some(&4));
+    }
+}
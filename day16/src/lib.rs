@@ -0,0 +1,297 @@
+use std::error::Error;
+use std::thread;
+
+use aoc::{Answer, Solution};
+
+pub const BASE_PATTERN: [i64; 4] = [0, 1, 0, -1];
+
+pub fn parse(input: &str) -> Vec<u8> {
+    input
+        .trim()
+        .chars()
+        .map(|x| x.to_digit(10).expect("signal should only contain digits") as u8)
+        .collect()
+}
+
+pub fn to_string(digits: &[u8]) -> String {
+    digits.iter().map(|x| x.to_string()).collect()
+}
+
+// a phase engine for a given base pattern
+//
+// output digit i multiplies the input by the base pattern with each value
+// repeated i + 1 times, i.e. the input splits up into blocks of i + 1
+// digits which are all multiplied by the same value. with prefix sums each
+// block is a single subtraction, so digit i takes O(n / (i + 1)) and a
+// whole phase O(n log n)
+#[derive(Debug, Clone)]
+pub struct Fft {
+    pattern: Vec<i64>,
+    threads: usize,
+}
+
+impl Default for Fft {
+    fn default() -> Self {
+        Fft::new()
+    }
+}
+
+impl Fft {
+    pub fn new() -> Self {
+        Fft::with_pattern(BASE_PATTERN.to_vec())
+    }
+
+    pub fn with_pattern(pattern: Vec<i64>) -> Self {
+        assert!(!pattern.is_empty(), "the base pattern can't be empty");
+        Fft {
+            pattern,
+            threads: 1,
+        }
+    }
+
+    // the digits of a phase are independent, so can be shared out
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    pub fn phase(&self, signal: &[u8]) -> Vec<u8> {
+        self.phase_from(signal, 0)
+    }
+
+    pub fn phases(&self, signal: &[u8], phases: usize) -> Vec<u8> {
+        let mut output = signal.to_vec();
+        for _ in 0..phases {
+            output = self.phase(&output);
+        }
+        output
+    }
+
+    // the `length` digits starting at `offset` after running the phases
+    // over the signal repeated `repeat` times
+    //
+    // when the pattern starts with a 0, digit i ignores everything before
+    // position i, so only the digits from the offset onwards are needed,
+    // wherever the offset is. otherwise the whole signal has to be run
+    pub fn message(
+        &self,
+        signal: &[u8],
+        repeat: usize,
+        offset: usize,
+        phases: usize,
+        length: usize,
+    ) -> Vec<u8> {
+        let total = signal.len() * repeat;
+        assert!(
+            offset + length <= total,
+            "offset {} is past the end of the signal",
+            offset
+        );
+        let start = if self.pattern[0] == 0 { offset } else { 0 };
+
+        let mut output: Vec<u8> = signal
+            .iter()
+            .cycle()
+            .skip(start)
+            .take(total - start)
+            .cloned()
+            .collect();
+        for _ in 0..phases {
+            output = self.phase_from(&output, start);
+        }
+        output[offset - start..offset - start + length].to_vec()
+    }
+
+    // `suffix` is the signal from position `start` onwards, and all of the
+    // digits before it are treated as though they're multiplied by 0
+    fn phase_from(&self, suffix: &[u8], start: usize) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(suffix.len() + 1);
+        prefix.push(0);
+        let mut total = 0;
+        for &x in suffix {
+            total += x as i64;
+            prefix.push(total);
+        }
+
+        let mut output = vec![0; suffix.len()];
+        if self.threads == 1 {
+            self.digits(&prefix, start, 0, &mut output);
+            return output;
+        }
+
+        // the early digits have the most blocks, so deal out lots of small
+        // chunks round robin rather than giving the first thread all of them
+        let size = suffix.len().div_ceil(self.threads * 16).max(1);
+        let mut shares: Vec<Vec<(usize, &mut [u8])>> = (0..self.threads).map(|_| vec![]).collect();
+        for (i, chunk) in output.chunks_mut(size).enumerate() {
+            shares[i % self.threads].push((i * size, chunk));
+        }
+        thread::scope(|scope| {
+            for share in shares {
+                let prefix = &prefix;
+                scope.spawn(move || {
+                    for (from, chunk) in share {
+                        self.digits(prefix, start, from, chunk);
+                    }
+                });
+            }
+        });
+        output
+    }
+
+    // fills in `output` with the digits from position `start + from`
+    fn digits(&self, prefix: &[i64], start: usize, from: usize, output: &mut [u8]) {
+        let end = start + prefix.len() - 1;
+        let sum = |a: usize, b: usize| prefix[b.min(end) - start] - prefix[a.max(start) - start];
+
+        for (o, digit) in output.iter_mut().enumerate() {
+            let k = start + from + o + 1;
+            let mut value = 0;
+
+            // block j covers positions [j * k - 1, (j + 1) * k - 1)
+            let mut j = (start + 1) / k;
+            while j * k < end + 1 {
+                let multiplier = self.pattern[j % self.pattern.len()];
+                if multiplier != 0 {
+                    value += multiplier * sum((j * k).saturating_sub(1), (j + 1) * k - 1);
+                }
+                j += 1;
+            }
+            *digit = (value.abs() % 10) as u8;
+        }
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Vec<u8>;
+
+    fn parse(&self, input: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+        match input.trim().chars().find(|x| !x.is_ascii_digit()) {
+            Some(c) => Err(format!("unexpected {:?} in the signal", c).into()),
+            None => Ok(parse(input)),
+        }
+    }
+
+    fn part1(&self, signal: &Vec<u8>) -> Answer {
+        Ok(to_string(&fft().phases(signal, 100)[..8]))
+    }
+
+    // the message at the offset given by the first seven digits, in the
+    // signal repeated 10000 times
+    fn part2(&self, signal: &Vec<u8>) -> Answer {
+        let digits = signal
+            .get(..7)
+            .ok_or("the signal's too short for an offset")?;
+        let offset = digits.iter().fold(0, |n, &x| 10 * n + x as usize);
+        if offset + 8 > signal.len() * 10_000 {
+            return Err(format!("offset {} is past the end of the signal", offset).into());
+        }
+        Ok(to_string(&fft().message(signal, 10_000, offset, 100, 8)))
+    }
+}
+
+fn fft() -> Fft {
+    let threads = thread::available_parallelism().map_or(1, |x| x.get());
+    Fft::new().threads(threads)
+}
+
+#[cfg(test)]
+mod test {
+
+    // the original O(n^2) phase, to check against
+    fn naive(signal: &[u8], pattern: &[i64]) -> Vec<u8> {
+        (0..signal.len())
+            .map(|i| {
+                let value: i64 = signal
+                    .iter()
+                    .enumerate()
+                    .map(|(n, &x)| x as i64 * pattern[((n + 1) / (i + 1)) % pattern.len()])
+                    .sum();
+                (value.abs() % 10) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_example() {
+        use super::Fft;
+
+        let signal = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        assert_eq!(Fft::new().phase(&signal), vec![4, 8, 2, 2, 6, 1, 5, 8]);
+        assert_eq!(Fft::new().phases(&signal, 4), vec![0, 1, 0, 2, 9, 4, 9, 8]);
+    }
+
+    #[test]
+    fn test_loops() {
+        use super::{parse, to_string, Fft};
+
+        let fft = Fft::new();
+        for (input, expected) in [
+            ("80871224585914546619083218645595", "24176176"),
+            ("19617804207202209144916044189917", "73745418"),
+            ("69317163492948606335995924319873", "52432133"),
+        ] {
+            assert_eq!(to_string(&fft.phases(&parse(input), 100)[..8]), expected);
+        }
+    }
+
+    #[test]
+    fn test_message() {
+        use super::{parse, to_string, Fft};
+
+        let fft = Fft::new();
+        for (input, expected) in [
+            ("03036732577212944063491565474664", "84462026"),
+            ("02935109699940807407585447034323", "78725270"),
+            ("03081770884921959731165446850517", "53553731"),
+        ] {
+            let signal = parse(input);
+            let offset = input[..7].parse().unwrap();
+            let message = fft.message(&signal, 10_000, offset, 100, 8);
+            assert_eq!(to_string(&message), expected);
+        }
+    }
+
+    #[test]
+    fn test_message_first_half() {
+        use super::{parse, Fft};
+
+        let signal = parse("80871224585914546619083218645595");
+        let fft = Fft::new();
+        let full = fft.phases(&signal.repeat(3), 10);
+        for offset in [0, 5, 31, 47, 80] {
+            assert_eq!(
+                fft.message(&signal, 3, offset, 10, 8),
+                &full[offset..offset + 8]
+            );
+        }
+    }
+
+    #[test]
+    fn test_custom_pattern() {
+        use super::{parse, Fft};
+
+        let signal = parse("19617804207202209144916044189917");
+        for pattern in [vec![1, 2, -3], vec![0, 0, 1], vec![2]] {
+            let fft = Fft::with_pattern(pattern.clone());
+            assert_eq!(fft.phase(&signal), naive(&signal, &pattern));
+            assert_eq!(
+                fft.message(&signal, 2, 10, 3, 5),
+                &fft.phases(&signal.repeat(2), 3)[10..15]
+            );
+        }
+    }
+
+    #[test]
+    fn test_threads() {
+        use super::{parse, Fft, BASE_PATTERN};
+
+        let signal = parse("69317163492948606335995924319873").repeat(5);
+        let expected = naive(&signal, &BASE_PATTERN);
+        for threads in [1, 2, 3, 8, 500] {
+            assert_eq!(Fft::new().threads(threads).phase(&signal), expected);
+        }
+    }
+}
@@ -0,0 +1,342 @@
+extern crate intcode;
+
+use intcode::{Computer, Interupt, Program};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use aoc::{Answer, Solution};
+use aoc_utils::{Direction, Grid, Point, SparseGrid, YAxis};
+
+// the most characters the robot takes in a line of its movement logic
+const LINE: usize = 20;
+const FUNCTIONS: [char; 3] = ['A', 'B', 'C'];
+
+// a stretch of scaffold between two neighbouring squares, either way round
+type Edge = (Point, Point);
+
+fn edge(a: Point, b: Point) -> Edge {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Instruction {
+    TurnLeft,
+    TurnRight,
+    Forward(isize),
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::TurnLeft => write!(f, "L"),
+            Instruction::TurnRight => write!(f, "R"),
+            Instruction::Forward(steps) => write!(f, "{}", steps),
+        }
+    }
+}
+
+fn join<T: fmt::Display>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+#[derive(Clone, Copy)]
+struct Bot {
+    position: Point,
+    facing: Direction,
+}
+
+impl Bot {
+    fn go(&mut self, instruction: Instruction) {
+        match instruction {
+            Instruction::TurnLeft => self.facing = self.facing.turn_left(),
+            Instruction::TurnRight => self.facing = self.facing.turn_right(),
+            Instruction::Forward(steps) => self.position = self.position.go(self.facing, steps),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Tile {
+    Space,
+    Scaffold,
+    Bot(Direction),
+}
+
+struct Image {
+    layout: SparseGrid<Tile>,
+    bot: Bot,
+}
+
+impl Image {
+    fn read(computer: Computer) -> Image {
+        // rows go down the screen, but the bot turns with y going up
+        let camera: String = computer.map(|x| x as u8 as char).collect();
+        let image = Grid::parse(&camera, YAxis::Up, |c| match c {
+            '.' => Some(Tile::Space),
+            '#' => Some(Tile::Scaffold),
+            c => Direction::from_arrow(c).map(Tile::Bot),
+        })
+        .unwrap_or_else(|e| panic!("{}", e));
+        let bot = image
+            .iter()
+            .find_map(|(position, &x)| match x {
+                Tile::Bot(facing) => Some(Bot { position, facing }),
+                _ => None,
+            })
+            .unwrap();
+
+        Image {
+            layout: image.into_sparse(|&x| x != Tile::Space),
+            bot,
+        }
+    }
+
+    fn is_intersection(&self, pos: &Point) -> bool {
+        self.layout.contains(*pos) && self.layout.neighbours(*pos).count() == 4
+    }
+
+    fn calibration(&self) -> isize {
+        self.layout
+            .points()
+            .filter(|x| self.is_intersection(x))
+            .map(|x| x.0 * -x.1)
+            .sum()
+    }
+
+    // walks the bot over all of the scaffold, without going along any of it
+    // twice, until the path fits into movement functions. it has to follow
+    // the scaffold round corners, but at intersections can go straight on
+    // or turn
+    fn routines(&self) -> Option<Routines> {
+        self.walk(self.bot, &mut HashSet::new(), vec![])
+    }
+
+    fn walk(&self, bot: Bot, used: &mut HashSet<Edge>, path: Vec<Instruction>) -> Option<Routines> {
+        let ways: Vec<(Option<Instruction>, Point)> = vec![
+            (None, bot.position.step(bot.facing)),
+            (
+                Some(Instruction::TurnLeft),
+                bot.position.step(bot.facing.turn_left()),
+            ),
+            (
+                Some(Instruction::TurnRight),
+                bot.position.step(bot.facing.turn_right()),
+            ),
+        ];
+        let mut ways: Vec<_> = ways
+            .into_iter()
+            .filter(|&(_, x)| self.layout.contains(x) && !used.contains(&edge(bot.position, x)))
+            .collect();
+        if !self.is_intersection(&bot.position) {
+            ways.truncate(1);
+        }
+        if ways.is_empty() {
+            let visited: HashSet<_> = used.iter().flat_map(|&(x, y)| vec![x, y]).collect();
+            if visited.len() < self.layout.len() {
+                return None;
+            }
+            return compress(&path);
+        }
+
+        for (turn, next) in ways {
+            let step = edge(bot.position, next);
+            let mut bot = bot;
+            let mut path = path.clone();
+            if let Some(turn) = turn {
+                bot.go(turn);
+                path.push(turn);
+            }
+            bot.go(Instruction::Forward(1));
+            match path.last_mut() {
+                Some(Instruction::Forward(steps)) => *steps += 1,
+                _ => path.push(Instruction::Forward(1)),
+            }
+
+            used.insert(step);
+            let routines = self.walk(bot, used, path);
+            used.remove(&step);
+            if routines.is_some() {
+                return routines;
+            }
+        }
+        None
+    }
+}
+
+// the path split up into a main routine calling up to three movement
+// functions, each short enough to fit on a line
+#[derive(Debug, PartialEq)]
+struct Routines {
+    main: Vec<usize>,
+    functions: Vec<Vec<Instruction>>,
+}
+
+impl Routines {
+    // the robot's movement logic, as it expects to be sent it
+    fn encode(&self) -> String {
+        let main: Vec<char> = self.main.iter().map(|&x| FUNCTIONS[x]).collect();
+        let mut lines = vec![join(&main)];
+        for i in 0..FUNCTIONS.len() {
+            lines.push(self.functions.get(i).map_or(String::new(), |x| join(x)));
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+fn compress(path: &[Instruction]) -> Option<Routines> {
+    let mut routines = Routines {
+        main: vec![],
+        functions: vec![],
+    };
+    if compress_from(path, &mut routines) {
+        Some(routines)
+    } else {
+        None
+    }
+}
+
+// covers the rest of the path by calling a function which matches the
+// start of it, or by defining a new one, backtracking when stuck
+fn compress_from(path: &[Instruction], routines: &mut Routines) -> bool {
+    if path.is_empty() {
+        return true;
+    }
+    // each call takes a character and a comma
+    if 2 * (routines.main.len() + 1) - 1 > LINE {
+        return false;
+    }
+
+    for i in 0..routines.functions.len() {
+        let function = &routines.functions[i];
+        if path.starts_with(function) {
+            let rest = &path[function.len()..];
+            routines.main.push(i);
+            if compress_from(rest, routines) {
+                return true;
+            }
+            routines.main.pop();
+        }
+    }
+
+    if routines.functions.len() < FUNCTIONS.len() {
+        let mut length = 1;
+        while length <= path.len() && join(&path[..length]).len() <= LINE {
+            routines.functions.push(path[..length].to_vec());
+            routines.main.push(routines.functions.len() - 1);
+            if compress_from(&path[length..], routines) {
+                return true;
+            }
+            routines.main.pop();
+            routines.functions.pop();
+            length += 1;
+        }
+    }
+    false
+}
+
+// runs the program on the input, one character at a time, and gives the
+// last thing it outputs
+fn run(program: Program, input: &str) -> Option<isize> {
+    let mut input = input.bytes();
+    let mut last = None;
+    let mut state = program.execute();
+    loop {
+        state = match state {
+            Interupt::Halt => return last,
+            Interupt::Input(waiting) => waiting.input(input.next()? as isize).execute(),
+            Interupt::Output(output) => {
+                let (program, value) = output.receive();
+                last = Some(value);
+                program.execute()
+            }
+        };
+    }
+}
+
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let image = self.layout.render(YAxis::Up, |p, x| {
+            if p == self.bot.position {
+                self.bot.facing.arrow()
+            } else if x.is_some() {
+                '#'
+            } else {
+                ' '
+            }
+        });
+        write!(f, "{}", image)
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Program;
+
+    fn parse(&self, input: &str) -> Result<Program, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    // the sum of the alignment parameters of the scaffold intersections
+    fn part1(&self, program: &Program) -> Answer {
+        let image = Image::read(Computer::new(program.clone()));
+        Ok(image.calibration().to_string())
+    }
+
+    // the dust collected walking the robot over all of the scaffold
+    fn part2(&self, program: &Program) -> Answer {
+        let image = Image::read(Computer::new(program.clone()));
+        let routines = image
+            .routines()
+            .ok_or("no path over the scaffold fits into three functions")?;
+
+        let mut program = program.clone();
+        program.set(0, 2);
+        let dust = run(program, &format!("{}n\n", routines.encode()))
+            .ok_or("the robot didn't report the dust")?;
+        Ok(dust.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_compress() {
+        use super::Instruction::{Forward, TurnLeft, TurnRight};
+        use super::{compress, join};
+
+        // the path from the example
+        let path: Vec<_> = "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2"
+            .split(',')
+            .map(|x| match x {
+                "L" => TurnLeft,
+                "R" => TurnRight,
+                steps => Forward(steps.parse().unwrap()),
+            })
+            .collect();
+        let routines = compress(&path).unwrap();
+
+        let expanded: Vec<_> = routines
+            .main
+            .iter()
+            .flat_map(|&x| routines.functions[x].iter().copied())
+            .collect();
+        assert_eq!(expanded, path);
+        assert!(routines.functions.len() <= 3);
+        assert!(routines
+            .encode()
+            .lines()
+            .all(|x| !x.is_empty() && x.len() <= 20));
+        assert_eq!(join(&routines.functions[0][..2]), "R,8");
+    }
+}
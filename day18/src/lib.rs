@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::{Answer, Solution};
+pub use aoc_utils::Point;
+
+pub mod route;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Wall,
+    Open,
+    Entrance,
+    // both stored as the lower case letter, so a door and its key match
+    Door(char),
+    Key(char),
+}
+
+// keys and doors as a set of bits, bit 0 for a/A up to bit 25 for z/Z
+pub fn bit(c: char) -> u32 {
+    1 << (c.to_ascii_lowercase() as u32 - 'a' as u32)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub found: char,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unexpected char '{}' at line {}, column {}",
+            self.found, self.line, self.column
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vault {
+    width: usize,
+    height: usize,
+    tiles: Vec<Tile>,
+}
+
+impl FromStr for Vault {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = input.lines().filter(|x| !x.trim().is_empty()).collect();
+        let width = lines.iter().map(|x| x.len()).max().unwrap_or(0);
+        let mut tiles = vec![Tile::Wall; width * lines.len()];
+
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                tiles[y * width + x] = match c {
+                    '#' => Tile::Wall,
+                    '.' => Tile::Open,
+                    '@' => Tile::Entrance,
+                    'A'..='Z' => Tile::Door(c.to_ascii_lowercase()),
+                    'a'..='z' => Tile::Key(c),
+                    found => {
+                        return Err(ParseError {
+                            line: y + 1,
+                            column: x + 1,
+                            found,
+                        })
+                    }
+                };
+            }
+        }
+
+        Ok(Vault {
+            width,
+            height: lines.len(),
+            tiles,
+        })
+    }
+}
+
+impl Vault {
+    // anything off the map is a wall
+    pub fn get(&self, p: Point) -> Tile {
+        if p.0 < 0 || p.1 < 0 || p.0 as usize >= self.width || p.1 as usize >= self.height {
+            return Tile::Wall;
+        }
+        self.tiles[p.1 as usize * self.width + p.0 as usize]
+    }
+
+    fn set(&mut self, p: Point, tile: Tile) {
+        self.tiles[p.1 as usize * self.width + p.0 as usize] = tile;
+    }
+
+    fn positions(&self) -> impl Iterator<Item = (Point, Tile)> + '_ {
+        self.tiles.iter().enumerate().map(move |(i, &tile)| {
+            (
+                Point((i % self.width) as isize, (i / self.width) as isize),
+                tile,
+            )
+        })
+    }
+
+    // in reading order
+    pub fn entrances(&self) -> Vec<Point> {
+        self.positions()
+            .filter(|&(_, tile)| tile == Tile::Entrance)
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    pub fn keys(&self) -> BTreeMap<char, Point> {
+        self.positions()
+            .filter_map(|(p, tile)| match tile {
+                Tile::Key(c) => Some((c, p)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // the vault with the middle of it walled off and a robot in each
+    // quarter, i.e. the single entrance and the tiles around it become
+    //
+    //   @#@
+    //   ###
+    //   @#@
+    pub fn split(&self) -> Option<Vault> {
+        let entrances = self.entrances();
+        if entrances.len() != 1 {
+            return None;
+        }
+        let centre = entrances[0];
+        let mut vault = self.clone();
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let p = centre + Point(dx, dy);
+                if p.0 < 0 || p.1 < 0 || p.0 as usize >= self.width || p.1 as usize >= self.height {
+                    return None;
+                }
+                let tile = if dx != 0 && dy != 0 {
+                    Tile::Entrance
+                } else {
+                    Tile::Wall
+                };
+                vault.set(p, tile);
+            }
+        }
+        Some(vault)
+    }
+}
+
+impl fmt::Display for Vault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height {
+            let line: String = (0..self.width)
+                .map(|x| match self.get(Point(x as isize, y as isize)) {
+                    Tile::Wall => '#',
+                    Tile::Open => '.',
+                    Tile::Entrance => '@',
+                    Tile::Door(c) => c.to_ascii_uppercase(),
+                    Tile::Key(c) => c,
+                })
+                .collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vault;
+
+    fn parse(&self, input: &str) -> Result<Vault, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, vault: &Vault) -> Answer {
+        let steps = route::shortest(vault).ok_or("can't collect all the keys")?;
+        Ok(steps.to_string())
+    }
+
+    // with the entrance split into four, each with its own robot
+    fn part2(&self, vault: &Vault) -> Answer {
+        let split = vault.split().ok_or("can't split the entrance")?;
+        let steps = route::shortest(&split).ok_or("can't collect all the keys")?;
+        Ok(steps.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE: &str = "#########
+#b.A.@.a#
+#########";
+
+    #[test]
+    fn test_parse() {
+        use super::{bit, ParseError, Point, Tile, Vault};
+
+        let vault: Vault = EXAMPLE.parse().unwrap();
+        assert_eq!(vault.get(Point(1, 1)), Tile::Key('b'));
+        assert_eq!(vault.get(Point(3, 1)), Tile::Door('a'));
+        assert_eq!(vault.get(Point(-1, 1)), Tile::Wall);
+        assert_eq!(vault.entrances(), vec![Point(5, 1)]);
+        assert_eq!(vault.keys().keys().collect::<String>(), "ab");
+        assert_eq!(vault.to_string(), format!("{}\n", EXAMPLE));
+        assert_eq!(bit('c'), 4);
+        assert_eq!(bit('C'), 4);
+
+        assert_eq!(
+            "#.#\n#@?".parse::<Vault>(),
+            Err(ParseError {
+                line: 2,
+                column: 3,
+                found: '?'
+            })
+        );
+    }
+
+    #[test]
+    fn test_split() {
+        use super::Vault;
+
+        let vault: Vault = "#######
+#a.#Cd#
+##...##
+##.@.##
+##...##
+#cB#Ab#
+#######"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            vault.split().unwrap().to_string(),
+            "#######
+#a.#Cd#
+##@#@##
+#######
+##@#@##
+#cB#Ab#
+#######
+"
+        );
+        assert_eq!(vault.split().unwrap().split(), None);
+    }
+}
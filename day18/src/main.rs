@@ -1,3 +1,5 @@
+use day18::Day18;
+
 fn main() {
-    println!("Hello, world!");
+    aoc::solve(18, &Day18);
 }
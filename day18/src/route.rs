@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+
+use aoc_utils::{bfs, dijkstra};
+
+use crate::{bit, Point, Tile, Vault};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge {
+    // the index of the key at the other end
+    pub to: usize,
+    pub distance: usize,
+    // the doors in the way, and the keys passed on the way
+    pub doors: u32,
+    pub keys: u32,
+}
+
+// the distances between the entrances and keys, ignoring the doors but
+// noting which are in the way. where the vault has loops there can be more
+// than one way between two places, e.g. a short one through a door and a
+// longer one around it, so there's an edge for each way which isn't both
+// longer and more blocked than another
+#[derive(Clone, Debug)]
+pub struct Graph {
+    // the entrances first, then the keys in alphabetical order
+    nodes: Vec<Point>,
+    keys: Vec<char>,
+    edges: Vec<Vec<Edge>>,
+}
+
+impl Graph {
+    pub fn new(vault: &Vault) -> Self {
+        let entrances = vault.entrances();
+        let keys = vault.keys();
+        let mut nodes = entrances.clone();
+        nodes.extend(keys.values());
+
+        let index: HashMap<Point, usize> = nodes.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+        let edges = nodes
+            .iter()
+            .map(|&start| edges_from(vault, start, &index))
+            .collect();
+
+        Graph {
+            nodes,
+            keys: keys.keys().cloned().collect(),
+            edges,
+        }
+    }
+
+    pub fn robots(&self) -> usize {
+        self.nodes.len() - self.keys.len()
+    }
+
+    // the key at the given node, if it isn't an entrance
+    pub fn key(&self, node: usize) -> Option<char> {
+        node.checked_sub(self.robots()).map(|i| self.keys[i])
+    }
+
+    pub fn edges(&self, node: usize) -> &[Edge] {
+        &self.edges[node]
+    }
+
+    // the distance to a key, if there's a way to it at all
+    pub fn distance(&self, from: usize, key: char) -> Option<usize> {
+        self.edges[from]
+            .iter()
+            .find(|e| self.key(e.to) == Some(key))
+            .map(|e| e.distance)
+    }
+
+    // the fewest steps for the robots to collect every key, searching over
+    // where each robot is and which keys have been collected so far
+    pub fn shortest(&self) -> Option<usize> {
+        let all = self.keys.iter().fold(0, |keys, &c| keys | bit(c));
+        let start: (Vec<usize>, u32) = ((0..self.robots()).collect(), 0);
+
+        let moves = |(robots, collected): &(Vec<usize>, u32)| {
+            let mut moves = vec![];
+            for (i, &node) in robots.iter().enumerate() {
+                for edge in self.edges[node].iter() {
+                    let key = bit(self.key(edge.to).unwrap());
+                    // walking past a key picks it up, so going straight
+                    // past it to another is just the same as stopping at it
+                    if collected & key != 0 || (edge.doors | edge.keys) & !collected != 0 {
+                        continue;
+                    }
+                    let mut next = robots.clone();
+                    next[i] = edge.to;
+                    moves.push(((next, collected | key), edge.distance));
+                }
+            }
+            moves
+        };
+        let search = dijkstra(vec![start], moves, |(_, collected)| *collected == all);
+        search.goal().and_then(|x| search.cost(x))
+    }
+}
+
+// a breadth first search from the start to every key it can reach, noting
+// the doors and keys on the way to each one. the search is over places
+// along with what's been passed to get there, but a way to somewhere is
+// dropped if another got there at least as soon past no more doors or keys
+fn edges_from(vault: &Vault, start: Point, index: &HashMap<Point, usize>) -> Vec<Edge> {
+    let mut seen: HashMap<Point, Vec<u32>> = HashMap::new();
+    seen.insert(start, vec![0]);
+    let open = |&(p, doors, keys): &(Point, u32, u32)| -> Vec<(Point, u32, u32)> {
+        let (doors, keys) = match vault.get(p) {
+            Tile::Door(c) if p != start => (doors | bit(c), keys),
+            Tile::Key(c) if p != start => (doors, keys | bit(c)),
+            _ => (doors, keys),
+        };
+        let mut next = vec![];
+        for x in p.adjacent() {
+            if vault.get(x) == Tile::Wall {
+                continue;
+            }
+            let ways = seen.entry(x).or_default();
+            if ways.iter().any(|&w| w & !(doors | keys) == 0) {
+                continue;
+            }
+            ways.push(doors | keys);
+            next.push((x, doors, keys));
+        }
+        next
+    };
+    let search = bfs(vec![(start, 0, 0)], open, |_| false);
+
+    let mut edges: Vec<Edge> = search
+        .costs()
+        .iter()
+        .filter(|(&(p, _, _), _)| p != start && matches!(vault.get(p), Tile::Key(_)))
+        .map(|(&(p, doors, keys), &distance)| Edge {
+            to: index[&p],
+            distance,
+            doors,
+            keys,
+        })
+        .collect();
+    edges.sort_by_key(|x| (x.distance, x.to, x.doors, x.keys));
+    edges
+}
+
+pub fn shortest(vault: &Vault) -> Option<usize> {
+    Graph::new(vault).shortest()
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_graph() {
+        use super::Graph;
+        use crate::{bit, Vault};
+
+        let vault: Vault = "########################
+#f.D.E.e.C.b.A.@.a.B.c.#
+######################.#
+#d.....................#
+########################"
+            .parse()
+            .unwrap();
+        let graph = Graph::new(&vault);
+        assert_eq!(graph.robots(), 1);
+        assert_eq!(graph.distance(0, 'a'), Some(2));
+        assert_eq!(graph.distance(0, 'f'), Some(14));
+        let to_f = graph.edges(0).iter().find(|e| e.distance == 14).unwrap();
+        assert_eq!(to_f.doors, bit('a') | bit('c') | bit('d') | bit('e'));
+        assert_eq!(to_f.keys, bit('b') | bit('e'));
+        // from key a, the first node after the entrance
+        assert_eq!(graph.key(1), Some('a'));
+        assert_eq!(graph.distance(1, 'd'), Some(28));
+    }
+
+    #[test]
+    fn test_loops() {
+        use super::{shortest, Graph};
+        use crate::{bit, Vault};
+
+        // the short way to b is through a door with no key, but there's a
+        // longer way round
+        let vault: Vault = "#########
+#@.A.b..#
+#.#####.#
+#.......#
+#########"
+            .parse()
+            .unwrap();
+        let graph = Graph::new(&vault);
+        let ways: Vec<(usize, u32)> = graph
+            .edges(0)
+            .iter()
+            .map(|e| (e.distance, e.doors))
+            .collect();
+        assert_eq!(ways, vec![(4, bit('a')), (12, 0)]);
+        assert_eq!(shortest(&vault), Some(12));
+    }
+
+    #[test]
+    fn test_shortest() {
+        use super::shortest;
+        use crate::Vault;
+
+        for (input, expected) in [
+            ("#########\n#b.A.@.a#\n#########", 8),
+            (
+                "########################
+#f.D.E.e.C.b.A.@.a.B.c.#
+######################.#
+#d.....................#
+########################",
+                86,
+            ),
+            (
+                "########################
+#...............b.C.D.f#
+#.######################
+#.....@.a.B.c.d.A.e.F.g#
+########################",
+                132,
+            ),
+            (
+                "#################
+#i.G..c...e..H.p#
+########.########
+#j.A..b...f..D.o#
+########@########
+#k.E..a...g..B.n#
+########.########
+#l.F..d...h..C.m#
+#################",
+                136,
+            ),
+            (
+                "########################
+#@..............ac.GI.b#
+###d#e#f################
+###A#B#C################
+###g#h#i################
+########################",
+                81,
+            ),
+        ] {
+            let vault: Vault = input.parse().unwrap();
+            assert_eq!(shortest(&vault), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_shortest_split() {
+        use super::shortest;
+        use crate::Vault;
+
+        let vault: Vault = "#######
+#a.#Cd#
+##...##
+##.@.##
+##...##
+#cB#Ab#
+#######"
+            .parse()
+            .unwrap();
+        assert_eq!(shortest(&vault.split().unwrap()), Some(8));
+
+        for (input, expected) in [
+            (
+                "###############
+#d.ABC.#.....a#
+######@#@######
+###############
+######@#@######
+#b.....#.....c#
+###############",
+                24,
+            ),
+            (
+                "#############
+#DcBa.#.GhKl#
+#.###@#@#I###
+#e#d#####j#k#
+###C#@#@###J#
+#fEbA.#.FgHi#
+#############",
+                32,
+            ),
+            (
+                "#############
+#g#f.D#..h#l#
+#F###e#E###.#
+#dCba@#@BcIJ#
+#############
+#nK.L@#@G...#
+#M###N#H###.#
+#o#m..#i#jk.#
+#############",
+                72,
+            ),
+        ] {
+            let vault: Vault = input.parse().unwrap();
+            assert_eq!(shortest(&vault), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_unreachable() {
+        use super::shortest;
+        use crate::Vault;
+
+        // the key for A is behind A
+        let vault: Vault = "#######\n#@.A.a#\n#######".parse().unwrap();
+        assert_eq!(shortest(&vault), None);
+        let vault: Vault = "#####\n#@#a#\n#####".parse().unwrap();
+        assert_eq!(shortest(&vault), None);
+        let vault: Vault = "###\n#@#\n###".parse().unwrap();
+        assert_eq!(shortest(&vault), Some(0));
+    }
+}
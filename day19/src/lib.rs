@@ -0,0 +1,121 @@
+extern crate intcode;
+
+use intcode::{Computer, Program};
+use std::error::Error;
+
+use aoc::{Answer, Solution};
+use aoc_utils::Point;
+
+#[derive(Eq, PartialEq)]
+enum Beam {
+    Outside,
+    Inside,
+}
+
+struct Square {
+    top_left: Point<usize>,
+    size: usize,
+}
+
+impl Square {
+    fn new(size: usize) -> Self {
+        Square {
+            top_left: Point::origin(),
+            size,
+        }
+    }
+
+    fn top_right(&self) -> Point<usize> {
+        self.top_left + Point(self.size - 1, 0)
+    }
+
+    fn bottom_left(&self) -> Point<usize> {
+        self.top_left + Point(0, self.size - 1)
+    }
+
+    fn right(&mut self) {
+        self.top_left += Point(1, 0)
+    }
+
+    fn down(&mut self) {
+        self.top_left += Point(0, 1)
+    }
+}
+
+struct Bot {
+    program: Program,
+}
+
+impl Bot {
+    fn test(&self, position: Point<usize>) -> Beam {
+        let mut computer = Computer::new(self.program.clone());
+        computer.input(position.0 as isize).unwrap();
+        computer.input(position.1 as isize).unwrap();
+        match computer.output().unwrap() {
+            0 => Beam::Outside,
+            1 => Beam::Inside,
+            _ => panic!("unexpected response"),
+        }
+    }
+}
+
+fn beam_in_square(bot: &Bot, size: usize) -> usize {
+    let mut coverage = 0;
+
+    for x in 0..size {
+        for y in 0..size {
+            coverage += bot.test(Point(x, y)) as isize;
+        }
+    }
+
+    coverage as usize
+}
+
+fn find_fit(bot: &Bot, square: &mut Square) -> Point<usize> {
+    // the trick here is, for the square to be fully inside the beam
+    // it's sufficient to check only the top-right and bottom-left corners
+    // so we track down and right until we reach a point
+    // those are both inside, then return the location of the top-left
+    loop {
+        while bot.test(square.top_right()) != Beam::Inside {
+            square.down();
+        }
+
+        while bot.test(square.bottom_left()) != Beam::Inside {
+            square.right();
+        }
+
+        if bot.test(square.top_right()) == Beam::Inside {
+            break;
+        }
+    }
+    square.top_left
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Program;
+
+    fn parse(&self, input: &str) -> Result<Program, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    // the points the beam affects in the 50x50 area closest to the emitter
+    fn part1(&self, program: &Program) -> Answer {
+        let bot = Bot {
+            program: program.clone(),
+        };
+        Ok(beam_in_square(&bot, 50).to_string())
+    }
+
+    // the closest 100x100 square which fits entirely in the beam
+    fn part2(&self, program: &Program) -> Answer {
+        let bot = Bot {
+            program: program.clone(),
+        };
+        let mut square = Square::new(100);
+        let fit = find_fit(&bot, &mut square);
+        Ok((fit.0 * 10_000 + fit.1).to_string())
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::{Answer, Solution};
+pub use aoc_utils::Point;
+
+pub mod search;
+
+pub const START: &str = "AA";
+pub const END: &str = "ZZ";
+// how far down part 2 looks for a way out before giving up
+pub const DEEPEST: usize = 200;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MazeError {
+    // a letter which isn't part of a label next to the maze
+    Label { line: usize, column: usize },
+    // a label which isn't used exactly twice, or AA/ZZ not exactly once
+    Unpaired(String),
+    Missing(String),
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MazeError::Label { line, column } => {
+                write!(f, "stray label at line {}, column {}", line, column)
+            }
+            MazeError::Unpaired(label) => write!(f, "portal {} isn't a pair", label),
+            MazeError::Missing(label) => write!(f, "no {} in the maze", label),
+        }
+    }
+}
+
+impl Error for MazeError {}
+
+// which edge of the donut a portal is on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Side {
+    Inner,
+    Outer,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Portal {
+    pub label: String,
+    // the open tile next to the label
+    pub position: Point,
+    pub side: Side,
+}
+
+#[derive(Clone, Debug)]
+pub struct Maze {
+    open: HashSet<Point>,
+    portals: Vec<Portal>,
+}
+
+impl FromStr for Maze {
+    type Err = MazeError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<Vec<char>> = input.lines().map(|x| x.chars().collect()).collect();
+        let get = |p: Point| -> char {
+            if p.0 < 0 || p.1 < 0 {
+                return ' ';
+            }
+            lines
+                .get(p.1 as usize)
+                .and_then(|x| x.get(p.0 as usize))
+                .copied()
+                .unwrap_or(' ')
+        };
+
+        let mut open = HashSet::new();
+        let mut letters = vec![];
+        for (y, line) in lines.iter().enumerate() {
+            for (x, &c) in line.iter().enumerate() {
+                let p = Point(x as isize, y as isize);
+                match c {
+                    '.' => {
+                        open.insert(p);
+                    }
+                    'A'..='Z' => letters.push(p),
+                    _ => (),
+                }
+            }
+        }
+
+        // the outer edge of the maze is the box around the open tiles
+        let min_x = open.iter().map(|p| p.0).min().unwrap_or(0);
+        let max_x = open.iter().map(|p| p.0).max().unwrap_or(0);
+        let min_y = open.iter().map(|p| p.1).min().unwrap_or(0);
+        let max_y = open.iter().map(|p| p.1).max().unwrap_or(0);
+
+        // labels read left to right or top to bottom, with the maze on
+        // one side or the other
+        let mut portals = vec![];
+        let mut used = HashSet::new();
+        for &p in letters.iter() {
+            for step in [Point(1, 0), Point(0, 1)] {
+                let second = p + step;
+                if !get(second).is_ascii_uppercase() {
+                    continue;
+                }
+                let before = p - step;
+                let after = second + step;
+                let position = if open.contains(&after) {
+                    after
+                } else if open.contains(&before) {
+                    before
+                } else {
+                    continue;
+                };
+                let outer = position.0 == min_x
+                    || position.0 == max_x
+                    || position.1 == min_y
+                    || position.1 == max_y;
+                portals.push(Portal {
+                    label: [get(p), get(second)].iter().collect(),
+                    position,
+                    side: if outer { Side::Outer } else { Side::Inner },
+                });
+                used.insert(p);
+                used.insert(second);
+            }
+        }
+
+        if let Some(p) = letters.iter().find(|p| !used.contains(p)) {
+            return Err(MazeError::Label {
+                line: p.1 as usize + 1,
+                column: p.0 as usize + 1,
+            });
+        }
+
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for portal in portals.iter() {
+            *counts.entry(&portal.label).or_insert(0) += 1;
+        }
+        for label in [START, END] {
+            match counts.remove(label) {
+                Some(1) => (),
+                Some(_) => return Err(MazeError::Unpaired(label.to_string())),
+                None => return Err(MazeError::Missing(label.to_string())),
+            }
+        }
+        if let Some((label, _)) = counts.iter().find(|(_, &n)| n != 2) {
+            return Err(MazeError::Unpaired(label.to_string()));
+        }
+
+        portals.sort_by(|a, b| (&a.label, a.side).cmp(&(&b.label, b.side)));
+        Ok(Maze { open, portals })
+    }
+}
+
+impl Maze {
+    pub fn is_open(&self, p: Point) -> bool {
+        self.open.contains(&p)
+    }
+
+    // sorted by label, with the inner of each pair first
+    pub fn portals(&self) -> &[Portal] {
+        &self.portals
+    }
+
+    pub fn portal(&self, label: &str, side: Side) -> Option<&Portal> {
+        self.portals
+            .iter()
+            .find(|x| x.label == label && x.side == side)
+    }
+
+    // the other end of a portal, if it goes anywhere
+    pub fn partner(&self, portal: &Portal) -> Option<&Portal> {
+        self.portals
+            .iter()
+            .find(|x| x.label == portal.label && x.side != portal.side)
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Maze;
+
+    fn parse(&self, input: &str) -> Result<Maze, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, maze: &Maze) -> Answer {
+        let steps =
+            search::shortest(maze, search::Mode::Flat, 0).ok_or("no way through the maze")?;
+        Ok(steps.to_string())
+    }
+
+    // with the inner portals going down a level and the outer ones up
+    fn part2(&self, maze: &Maze) -> Answer {
+        let steps = search::shortest(maze, search::Mode::Recursive, DEEPEST)
+            .ok_or_else(|| format!("no way through the maze within {} levels", DEEPEST))?;
+        Ok(steps.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE1: &str = "         A
+         A
+  #######.#########
+  #######.........#
+  #######.#######.#
+  #######.#######.#
+  #######.#######.#
+  #####  B    ###.#
+BC...##  C    ###.#
+  ##.##       ###.#
+  ##...DE  F  ###.#
+  #####    G  ###.#
+  #########.#####.#
+DE..#######...###.#
+  #.#########.###.#
+FG..#########.....#
+  ###########.#####
+             Z
+             Z       ";
+
+    #[test]
+    fn test_parse() {
+        use super::{Maze, Point, Side};
+
+        let maze: Maze = EXAMPLE1.parse().unwrap();
+        let labels: Vec<(&str, Side)> = maze
+            .portals()
+            .iter()
+            .map(|x| (x.label.as_str(), x.side))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("AA", Side::Outer),
+                ("BC", Side::Inner),
+                ("BC", Side::Outer),
+                ("DE", Side::Inner),
+                ("DE", Side::Outer),
+                ("FG", Side::Inner),
+                ("FG", Side::Outer),
+                ("ZZ", Side::Outer),
+            ]
+        );
+        assert_eq!(
+            maze.portal("AA", Side::Outer).unwrap().position,
+            Point(9, 2)
+        );
+        assert_eq!(
+            maze.portal("BC", Side::Inner).unwrap().position,
+            Point(9, 6)
+        );
+        assert_eq!(
+            maze.portal("DE", Side::Inner).unwrap().position,
+            Point(6, 10)
+        );
+        assert_eq!(
+            maze.portal("FG", Side::Outer).unwrap().position,
+            Point(2, 15)
+        );
+        let bc = maze.portal("BC", Side::Outer).unwrap();
+        assert_eq!(maze.partner(bc).unwrap().position, Point(9, 6));
+        assert_eq!(maze.partner(maze.portal("ZZ", Side::Outer).unwrap()), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use super::{Maze, MazeError};
+
+        let no_end = EXAMPLE1.replace('Z', " ");
+        assert_eq!(
+            no_end.parse::<Maze>().unwrap_err(),
+            MazeError::Missing("ZZ".to_string())
+        );
+        let unpaired = EXAMPLE1.replace("FG..", "FH..");
+        assert_eq!(
+            unpaired.parse::<Maze>().unwrap_err(),
+            MazeError::Unpaired("FG".to_string())
+        );
+        let stray = EXAMPLE1.replacen("#########\n", "#########Q\n", 1);
+        assert_eq!(
+            stray.parse::<Maze>().unwrap_err().to_string(),
+            "stray label at line 3, column 20"
+        );
+    }
+}
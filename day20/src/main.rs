@@ -1,3 +1,5 @@
+use day20::Day20;
+
 fn main() {
-    println!("Hello, world!");
+    aoc::solve(20, &Day20);
 }
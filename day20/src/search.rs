@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use aoc_utils::{bfs, dijkstra};
+
+use crate::{Maze, Point, Side, END, START};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    // every level is the same maze, so there's only one
+    Flat,
+    // inner portals go down a level and outer portals back up, and the
+    // outer portals on the top level are walls
+    Recursive,
+}
+
+// the portals, with the walking distance between each pair of them which
+// are joined up without going through another portal
+#[derive(Clone, Debug)]
+pub struct Graph {
+    // indexed like the maze's portals
+    walks: Vec<Vec<(usize, usize)>>,
+    // the other end of each portal, and which way it changes level
+    warps: Vec<Option<(usize, isize)>>,
+    start: usize,
+    end: usize,
+}
+
+impl Graph {
+    pub fn new(maze: &Maze) -> Self {
+        let portals = maze.portals();
+        let index: HashMap<Point, usize> = portals
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (x.position, i))
+            .collect();
+
+        let walks = portals
+            .iter()
+            .map(|x| walks_from(maze, x.position, &index))
+            .collect();
+        let warps = portals
+            .iter()
+            .map(|x| {
+                maze.partner(x).map(|other| {
+                    let to = index[&other.position];
+                    match x.side {
+                        Side::Inner => (to, 1),
+                        Side::Outer => (to, -1),
+                    }
+                })
+            })
+            .collect();
+        let find = |label| portals.iter().position(|x| x.label == label).unwrap();
+
+        Graph {
+            walks,
+            warps,
+            start: find(START),
+            end: find(END),
+        }
+    }
+
+    // the fewest steps from AA to ZZ (on the top level), searching over
+    // the portals and levels. a way out can need any number of levels, so
+    // the recursive search gives up below the deepest level it's given
+    pub fn shortest(&self, mode: Mode, deepest: usize) -> Option<usize> {
+        let deepest = deepest as isize;
+        let moves = |&(node, level): &(usize, isize)| {
+            let mut moves: Vec<((usize, isize), usize)> = self.walks[node]
+                .iter()
+                .map(|&(to, steps)| ((to, level), steps))
+                .collect();
+            if let Some((to, change)) = self.warps[node] {
+                match mode {
+                    Mode::Flat => moves.push(((to, level), 1)),
+                    Mode::Recursive if (0..=deepest).contains(&(level + change)) => {
+                        moves.push(((to, level + change), 1))
+                    }
+                    Mode::Recursive => (),
+                }
+            }
+            moves
+        };
+        let end = (self.end, 0);
+        let search = dijkstra(vec![(self.start, 0)], moves, |&x| x == end);
+        search.goal().and_then(|x| search.cost(x))
+    }
+}
+
+// a breadth first search through the open tiles to every other portal
+fn walks_from(maze: &Maze, start: Point, index: &HashMap<Point, usize>) -> Vec<(usize, usize)> {
+    let open = |p: &Point| -> Vec<Point> {
+        p.adjacent()
+            .into_iter()
+            .filter(|&x| maze.is_open(x))
+            .collect()
+    };
+    let search = bfs(vec![start], open, |_| false);
+
+    let mut walks: Vec<(usize, usize)> = search
+        .costs()
+        .iter()
+        .filter(|(&p, _)| p != start)
+        .filter_map(|(p, &distance)| index.get(p).map(|&i| (i, distance)))
+        .collect();
+    walks.sort();
+    walks
+}
+
+pub fn shortest(maze: &Maze, mode: Mode, deepest: usize) -> Option<usize> {
+    Graph::new(maze).shortest(mode, deepest)
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE1: &str = "         A
+         A
+  #######.#########
+  #######.........#
+  #######.#######.#
+  #######.#######.#
+  #######.#######.#
+  #####  B    ###.#
+BC...##  C    ###.#
+  ##.##       ###.#
+  ##...DE  F  ###.#
+  #####    G  ###.#
+  #########.#####.#
+DE..#######...###.#
+  #.#########.###.#
+FG..#########.....#
+  ###########.#####
+             Z
+             Z       ";
+
+    const EXAMPLE2: &str = "                   A
+                   A
+  #################.#############
+  #.#...#...................#.#.#
+  #.#.#.###.###.###.#########.#.#
+  #.#.#.......#...#.....#.#.#...#
+  #.#########.###.#####.#.#.###.#
+  #.............#.#.....#.......#
+  ###.###########.###.#.#.#.#.###
+  #.....#        A   C    #.#.#.#
+  #######        S   P    #####.#
+  #.#...#                 #......VT
+  #.#.#.#                 #.#####
+  #...#.#               YN....#.#
+  #.###.#                 #####.#
+DI....#.#                 #.....#
+  #####.#                 #.###.#
+ZZ......#               QG....#..AS
+  ###.###                 #######
+JO..#.#.#                 #.....#
+  #.#.#.#                 ###.#.#
+  #...#..DI             BU....#..LF
+  #####.#                 #.#####
+YN......#               VT..#....QG
+  #.###.#                 #.###.#
+  #.#...#                 #.....#
+  ###.###    J L     J    #.#.###
+  #.....#    O F     P    #.#...#
+  #.###.#####.#.#####.#####.###.#
+  #...#.#.#...#.....#.....#.#...#
+  #.#####.###.###.#.#.#########.#
+  #...#.#.....#...#.#.#.#.....#.#
+  #.###.#####.###.###.#.#.#######
+  #.#.........#...#.............#
+  #########.###.###.#############
+           B   J   C
+           U   P   P";
+
+    const EXAMPLE3: &str = "             Z L X W       C                 
+             Z P Q B       K                 
+  ###########.#.#.#.#######.###############  
+  #...#.......#.#.......#.#.......#.#.#...#  
+  ###.#.#.#.#.#.#.#.###.#.#.#######.#.#.###  
+  #.#...#.#.#...#.#.#...#...#...#.#.......#  
+  #.###.#######.###.###.#.###.###.#.#######  
+  #...#.......#.#...#...#.............#...#  
+  #.#########.#######.#.#######.#######.###  
+  #...#.#    F       R I       Z    #.#.#.#  
+  #.###.#    D       E C       H    #.#.#.#  
+  #.#...#                           #...#.#  
+  #.###.#                           #.###.#  
+  #.#....OA                       WB..#.#..ZH
+  #.###.#                           #.#.#.#  
+CJ......#                           #.....#  
+  #######                           #######  
+  #.#....CK                         #......IC
+  #.###.#                           #.###.#  
+  #.....#                           #...#.#  
+  ###.###                           #.#.#.#  
+XF....#.#                         RF..#.#.#  
+  #####.#                           #######  
+  #......CJ                       NM..#...#  
+  ###.#.#                           #.###.#  
+RE....#.#                           #......RF
+  ###.###        X   X       L      #.#.#.#  
+  #.....#        F   Q       P      #.#.#.#  
+  ###.###########.###.#######.#########.###  
+  #.....#...#.....#.......#...#.....#.#...#  
+  #####.#.###.#######.#######.###.###.#.#.#  
+  #.......#.......#.#.#.#.#...#...#...#.#.#  
+  #####.###.#####.#.#.#.#.###.###.#.###.###  
+  #.......#.....#.#...#...............#...#  
+  #############.#.#.###.###################  
+               A O F   N                     
+               A A D   M                     ";
+
+    #[test]
+    fn test_flat() {
+        use super::{shortest, Mode};
+        use crate::Maze;
+
+        let maze: Maze = EXAMPLE1.parse().unwrap();
+        assert_eq!(shortest(&maze, Mode::Flat, 0), Some(23));
+        let maze: Maze = EXAMPLE2.parse().unwrap();
+        assert_eq!(shortest(&maze, Mode::Flat, 0), Some(58));
+    }
+
+    #[test]
+    fn test_recursive() {
+        use super::{shortest, Mode};
+        use crate::Maze;
+
+        let maze: Maze = EXAMPLE1.parse().unwrap();
+        assert_eq!(shortest(&maze, Mode::Recursive, 10), Some(26));
+        // going through the portals only ever goes deeper
+        let maze: Maze = EXAMPLE2.parse().unwrap();
+        assert_eq!(shortest(&maze, Mode::Recursive, 50), None);
+        // the way out goes ten levels down, so isn't found any shallower
+        let maze: Maze = EXAMPLE3.parse().unwrap();
+        assert_eq!(shortest(&maze, Mode::Recursive, 10), Some(396));
+        assert_eq!(shortest(&maze, Mode::Recursive, 9), None);
+    }
+
+    #[test]
+    fn test_graph() {
+        use super::Graph;
+        use crate::Maze;
+
+        let maze: Maze = EXAMPLE1.parse().unwrap();
+        let graph = Graph::new(&maze);
+        // AA can walk to the inner BC and FG, and to ZZ
+        let mut walks = graph.walks[graph.start].clone();
+        walks.sort();
+        assert_eq!(walks, vec![(1, 4), (5, 30), (7, 26)]);
+        assert_eq!(graph.warps[1], Some((2, 1)));
+        assert_eq!(graph.warps[2], Some((1, -1)));
+        assert_eq!(graph.warps[graph.end], None);
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+
+use intcode::{Interupt, Program};
+
+use crate::hull::Hull;
+use crate::{Script, ScriptError};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Report {
+    // the droid made it across, and this is the hull damage it found
+    Damage(isize),
+    // it fell in a hole, somewhere along this stretch of hull
+    Fell(Hull),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DroidError {
+    Script(ScriptError),
+    // the program asked for more than the script had in it
+    WantsInput,
+    // it didn't report any damage, but didn't draw where it fell either
+    NoHull(String),
+}
+
+impl fmt::Display for DroidError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DroidError::Script(e) => write!(f, "{}", e),
+            DroidError::WantsInput => write!(f, "the droid wants more input"),
+            DroidError::NoHull(text) => write!(f, "can't find the hull in:\n{}", text),
+        }
+    }
+}
+
+impl Error for DroidError {}
+
+impl From<ScriptError> for DroidError {
+    fn from(e: ScriptError) -> Self {
+        DroidError::Script(e)
+    }
+}
+
+// runs the droid's program with the given script for input, giving back
+// everything it printed and the last thing it output if that wasn't ascii
+fn run(program: &Program, input: &str) -> Result<(String, Option<isize>), DroidError> {
+    let mut input: VecDeque<isize> = input.bytes().map(|x| x as isize).collect();
+    let mut text = String::new();
+    let mut last = None;
+    let mut program = program.clone();
+    loop {
+        program = match program.execute() {
+            Interupt::Halt => return Ok((text, last)),
+            Interupt::Input(state) => state.input(input.pop_front().ok_or(DroidError::WantsInput)?),
+            Interupt::Output(state) => {
+                let (program, value) = state.receive();
+                match value {
+                    0..=127 => text.push(value as u8 as char),
+                    _ => last = Some(value),
+                }
+                program
+            }
+        };
+    }
+}
+
+pub fn send(program: &Program, script: &Script) -> Result<Report, DroidError> {
+    let (text, damage) = run(program, &script.encode()?)?;
+    if let Some(damage) = damage {
+        return Ok(Report::Damage(damage));
+    }
+
+    // it draws the droid falling in, starting with it standing on the hull
+    let hull = text
+        .lines()
+        .skip_while(|x| !x.starts_with("Didn't make it across"))
+        .find(|x| x.starts_with('#') && x.chars().all(|c| c == '#' || c == '.'))
+        .and_then(|x| x.parse().ok());
+    hull.map(Report::Fell).ok_or(DroidError::NoHull(text))
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_send_errors() {
+        use super::{send, DroidError};
+        use crate::Script;
+        use intcode::Program;
+
+        let script: Script = "NOT A J\nWALK".parse().unwrap();
+        // reads input forever
+        let greedy: Program = "3,10,1105,1,0".parse().unwrap();
+        assert_eq!(send(&greedy, &script), Err(DroidError::WantsInput));
+        // prints "#" then halts, without saying it fell
+        let quiet: Program = "104,35,99".parse().unwrap();
+        assert_eq!(
+            send(&quiet, &script),
+            Err(DroidError::NoHull("#".to_string()))
+        );
+
+        let far: Script = "NOT E J\nWALK".parse().unwrap();
+        assert!(matches!(send(&quiet, &far), Err(DroidError::Script(_))));
+    }
+}
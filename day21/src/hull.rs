@@ -0,0 +1,142 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Script, ScriptError};
+
+// how far the droid goes in one jump
+pub const JUMP: usize = 4;
+// and how far ahead it can see when running
+pub const RANGE: usize = 9;
+
+// a stretch of hull, with the droid starting on the first tile. past
+// the end it's solid ground forever
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Hull {
+    ground: Vec<bool>,
+}
+
+impl Hull {
+    pub fn len(&self) -> usize {
+        self.ground.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ground.is_empty()
+    }
+
+    pub fn is_ground(&self, position: usize) -> bool {
+        self.ground.get(position).copied().unwrap_or(true)
+    }
+
+    // what the sensors see from the given tile, bit 0 for the next tile
+    pub fn sensors(&self, position: usize) -> u16 {
+        (0..RANGE).fold(0, |bits, i| {
+            bits | (self.is_ground(position + 1 + i) as u16) << i
+        })
+    }
+
+    // takes the droid across, deciding whether to jump from what it can
+    // see. gives the tile it fell through if it doesn't make it
+    pub fn cross(&self, mut jumps: impl FnMut(u16) -> bool) -> Result<(), usize> {
+        let mut position = 0;
+        while position < self.len() {
+            if !self.is_ground(position) {
+                return Err(position);
+            }
+            position += if jumps(self.sensors(position)) {
+                JUMP
+            } else {
+                1
+            };
+        }
+        Ok(())
+    }
+
+    // the script's checked first, so it can't go wrong part way across
+    pub fn survives(&self, script: &Script) -> Result<Result<(), usize>, ScriptError> {
+        script.jumps(0)?;
+        Ok(self.cross(|sensors| script.jumps(sensors) == Ok(true)))
+    }
+}
+
+impl FromStr for Hull {
+    type Err = String;
+
+    // '#' for ground and '.' for a hole, as the droid draws it
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        input
+            .trim()
+            .chars()
+            .map(|c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(format!("unexpected {:?} in the hull", c)),
+            })
+            .collect::<Result<Vec<bool>, String>>()
+            .map(|ground| Hull { ground })
+    }
+}
+
+impl fmt::Display for Hull {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let line: String = self
+            .ground
+            .iter()
+            .map(|&x| if x { '#' } else { '.' })
+            .collect();
+        write!(f, "{}", line)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_sensors() {
+        use super::Hull;
+
+        let hull: Hull = "#####.#..########".parse().unwrap();
+        assert_eq!(hull.to_string(), "#####.#..########");
+        assert_eq!(hull.sensors(0), 0b100101111);
+        assert_eq!(hull.sensors(4), 0b111110010);
+        // past the end is all ground
+        assert_eq!(hull.sensors(16), 0b111111111);
+        assert!("##@#".parse::<Hull>().is_err());
+    }
+
+    #[test]
+    fn test_survives() {
+        use super::Hull;
+        use crate::Script;
+
+        let walk: Script = "NOT A J\nWALK".parse().unwrap();
+        let hull: Hull = "#####.###########".parse().unwrap();
+        assert_eq!(hull.survives(&walk), Ok(Ok(())));
+        let hull: Hull = "#####..#.########".parse().unwrap();
+        assert_eq!(hull.survives(&walk), Ok(Err(8)));
+
+        // jumping whenever there's a hole ahead it can land past
+        let script: Script = "NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J
+WALK"
+            .parse()
+            .unwrap();
+        for input in [
+            "#####..#.########",
+            "#####.#..########",
+            "#####...#########",
+        ] {
+            let hull: Hull = input.parse().unwrap();
+            assert_eq!(hull.survives(&script), Ok(Ok(())));
+        }
+        let never: Script = "WALK".parse().unwrap();
+        assert_eq!(
+            "###.#".parse::<Hull>().unwrap().survives(&never),
+            Ok(Err(3))
+        );
+    }
+}
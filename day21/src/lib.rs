@@ -0,0 +1,440 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::{Answer, Solution};
+use intcode::Program;
+
+use droid::Report;
+use hull::Hull;
+
+pub mod droid;
+pub mod hull;
+pub mod synth;
+
+// the most instructions the droid will take
+pub const LIMIT: usize = 15;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Register {
+    // the sensors, A for the ground one tile ahead up to I nine tiles ahead
+    Sensor(u8),
+    // the scratch register and the jump register, both false to start
+    T,
+    J,
+}
+
+impl Register {
+    pub fn sensor(c: char) -> Option<Register> {
+        match c {
+            'A'..='I' => Some(Register::Sensor(c as u8 - b'A')),
+            _ => None,
+        }
+    }
+
+    pub fn is_writable(self) -> bool {
+        !matches!(self, Register::Sensor(_))
+    }
+}
+
+impl FromStr for Register {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "T" => Ok(Register::T),
+            "J" => Ok(Register::J),
+            _ => {
+                let mut chars = input.chars();
+                match (chars.next().and_then(Register::sensor), chars.next()) {
+                    (Some(register), None) => Ok(register),
+                    _ => Err(format!("unknown register {:?}", input)),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Register::Sensor(n) => write!(f, "{}", (b'A' + n) as char),
+            Register::T => write!(f, "T"),
+            Register::J => write!(f, "J"),
+        }
+    }
+}
+
+// each writes its result to the second register
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    And(Register, Register),
+    Or(Register, Register),
+    Not(Register, Register),
+}
+
+impl Instruction {
+    fn registers(&self) -> (Register, Register) {
+        match *self {
+            Instruction::And(x, y) | Instruction::Or(x, y) | Instruction::Not(x, y) => (x, y),
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (op, x, y) = match words[..] {
+            [op, x, y] => (op, x.parse()?, y.parse()?),
+            _ => return Err(format!("expected an instruction, found {:?}", line.trim())),
+        };
+        if !Register::is_writable(y) {
+            return Err(format!("can't write to sensor {}", y));
+        }
+        match op {
+            "AND" => Ok(Instruction::And(x, y)),
+            "OR" => Ok(Instruction::Or(x, y)),
+            "NOT" => Ok(Instruction::Not(x, y)),
+            _ => Err(format!("unknown instruction {:?}", op)),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::And(x, y) => write!(f, "AND {} {}", x, y),
+            Instruction::Or(x, y) => write!(f, "OR {} {}", x, y),
+            Instruction::Not(x, y) => write!(f, "NOT {} {}", x, y),
+        }
+    }
+}
+
+// walking only lets the droid see sensors A to D, running all of them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Walk,
+    Run,
+}
+
+impl Mode {
+    pub fn sensors(self) -> u8 {
+        match self {
+            Mode::Walk => 4,
+            Mode::Run => 9,
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Walk => write!(f, "WALK"),
+            Mode::Run => write!(f, "RUN"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    TooLong(usize),
+    // a sensor the droid can't see in its mode
+    OutOfRange(Register),
+    // a sensor written to, which only the droid can do
+    ReadOnly(Register),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScriptError::TooLong(n) => {
+                write!(f, "{} instructions, but only {} fit", n, LIMIT)
+            }
+            ScriptError::OutOfRange(register) => {
+                write!(f, "sensor {} is out of range", register)
+            }
+            ScriptError::ReadOnly(register) => {
+                write!(f, "can't write to sensor {}", register)
+            }
+        }
+    }
+}
+
+impl Error for ScriptError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Script {
+    pub instructions: Vec<Instruction>,
+    pub mode: Mode,
+}
+
+impl Script {
+    pub fn new(instructions: Vec<Instruction>, mode: Mode) -> Self {
+        Script { instructions, mode }
+    }
+
+    // the script as the droid expects to be sent it, one instruction per
+    // line then the mode
+    pub fn encode(&self) -> Result<String, ScriptError> {
+        if self.instructions.len() > LIMIT {
+            return Err(ScriptError::TooLong(self.instructions.len()));
+        }
+        for instruction in self.instructions.iter() {
+            let (x, y) = instruction.registers();
+            if !y.is_writable() {
+                return Err(ScriptError::ReadOnly(y));
+            }
+            if let Register::Sensor(n) = x {
+                if n >= self.mode.sensors() {
+                    return Err(ScriptError::OutOfRange(x));
+                }
+            }
+        }
+        Ok(self.to_string())
+    }
+
+    // whether the droid jumps, given the ground it can see as bits, with
+    // bit 0 for sensor A
+    pub fn jumps(&self, sensors: u16) -> Result<bool, ScriptError> {
+        let (mut t, mut j) = (false, false);
+        for instruction in self.instructions.iter() {
+            let (x, y) = instruction.registers();
+            let x = match x {
+                Register::Sensor(n) => sensors >> n & 1 == 1,
+                Register::T => t,
+                Register::J => j,
+            };
+            let y = match y {
+                Register::T => &mut t,
+                Register::J => &mut j,
+                Register::Sensor(_) => return Err(ScriptError::ReadOnly(y)),
+            };
+            *y = match instruction {
+                Instruction::And(..) => x && *y,
+                Instruction::Or(..) => x || *y,
+                Instruction::Not(..) => !x,
+            };
+        }
+        Ok(j)
+    }
+}
+
+impl FromStr for Script {
+    type Err = ParseError;
+
+    // instructions one per line, finishing with WALK or RUN
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut instructions = vec![];
+        let lines = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        for (i, line) in lines {
+            let mode = match line.trim() {
+                "WALK" => Mode::Walk,
+                "RUN" => Mode::Run,
+                _ => {
+                    let instruction = line.parse().map_err(|message| ParseError {
+                        line: i + 1,
+                        message,
+                    })?;
+                    instructions.push(instruction);
+                    continue;
+                }
+            };
+            if let Some((i, _)) = input
+                .lines()
+                .enumerate()
+                .skip(i + 1)
+                .find(|(_, x)| !x.trim().is_empty())
+            {
+                return Err(ParseError {
+                    line: i + 1,
+                    message: format!("nothing can come after {}", mode),
+                });
+            }
+            return Ok(Script::new(instructions, mode));
+        }
+        Err(ParseError {
+            line: input.lines().count(),
+            message: "expected WALK or RUN at the end".to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for instruction in self.instructions.iter() {
+            writeln!(f, "{}", instruction)?;
+        }
+        writeln!(f, "{}", self.mode)
+    }
+}
+
+// keep working out a script which gets across every hull the droid has
+// fallen through so far, until it makes it. each hull fallen through and
+// the script which worked are logged on the way
+pub fn survey(
+    program: &Program,
+    mode: Mode,
+    mut log: impl FnMut(String),
+) -> Result<isize, Box<dyn Error>> {
+    let mut hulls: Vec<Hull> = vec![];
+    loop {
+        let script = synth::synthesise(&hulls, mode).ok_or("no script gets across")?;
+        match droid::send(program, &script)? {
+            Report::Damage(damage) => {
+                log(script.to_string());
+                return Ok(damage);
+            }
+            Report::Fell(hull) => {
+                log(format!("fell through {}\n", hull));
+                if hulls.contains(&hull) {
+                    return Err(format!("fell through {} again", hull).into());
+                }
+                hulls.push(hull);
+            }
+        }
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Program;
+
+    fn parse(&self, input: &str) -> Result<Program, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, program: &Program) -> Answer {
+        Ok(survey(program, Mode::Walk, |_| ())?.to_string())
+    }
+
+    fn part2(&self, program: &Program) -> Answer {
+        Ok(survey(program, Mode::Run, |_| ())?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE: &str = "NOT A J
+NOT B T
+AND T J
+NOT C T
+AND T J
+AND D J
+WALK
+";
+
+    #[test]
+    fn test_parse() {
+        use super::{Instruction, Mode, Register, Script};
+
+        let script: Script = EXAMPLE.parse().unwrap();
+        assert_eq!(script.mode, Mode::Walk);
+        assert_eq!(script.instructions.len(), 6);
+        assert_eq!(
+            script.instructions[1],
+            Instruction::Not(Register::Sensor(1), Register::T)
+        );
+        assert_eq!(script.to_string(), EXAMPLE);
+        assert_eq!("I".parse(), Ok(Register::Sensor(8)));
+        assert!("K".parse::<Register>().is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use super::Script;
+
+        for (input, expected) in [
+            ("NOT A B\nWALK", "line 1: can't write to sensor B"),
+            (
+                "NOT A J\nXOR A J\nRUN",
+                "line 2: unknown instruction \"XOR\"",
+            ),
+            ("NOT A J\n\nAND Q J\nRUN", "line 3: unknown register \"Q\""),
+            (
+                "NOT A J\nOR T",
+                "line 2: expected an instruction, found \"OR T\"",
+            ),
+            ("NOT A J", "line 1: expected WALK or RUN at the end"),
+            ("WALK\nNOT A J", "line 2: nothing can come after WALK"),
+        ] {
+            assert_eq!(input.parse::<Script>().unwrap_err().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        use super::{Instruction, Mode, Register, Script, ScriptError};
+
+        let script: Script = EXAMPLE.parse().unwrap();
+        assert_eq!(script.encode(), Ok(EXAMPLE.to_string()));
+
+        let far: Script = "NOT E J\nWALK".parse().unwrap();
+        assert_eq!(
+            far.encode(),
+            Err(ScriptError::OutOfRange(Register::Sensor(4)))
+        );
+        assert!("NOT E J\nRUN".parse::<Script>().unwrap().encode().is_ok());
+
+        let long: Script = format!("{}WALK", "NOT A J\n".repeat(16)).parse().unwrap();
+        assert_eq!(long.encode(), Err(ScriptError::TooLong(16)));
+
+        // only built by hand, as parsing won't allow it
+        let written = Script::new(
+            vec![Instruction::Not(Register::T, Register::Sensor(0))],
+            Mode::Walk,
+        );
+        assert_eq!(
+            written.encode(),
+            Err(ScriptError::ReadOnly(Register::Sensor(0)))
+        );
+        assert_eq!(
+            written.jumps(0),
+            Err(ScriptError::ReadOnly(Register::Sensor(0)))
+        );
+    }
+
+    #[test]
+    fn test_jumps() {
+        use super::Script;
+
+        // jump if there's a hole in A, B or C and ground at D
+        let script: Script = "NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J
+WALK"
+            .parse()
+            .unwrap();
+        assert_eq!(script.jumps(0b1111), Ok(false));
+        assert_eq!(script.jumps(0b1110), Ok(true));
+        assert_eq!(script.jumps(0b1011), Ok(true));
+        assert_eq!(script.jumps(0b0011), Ok(false));
+        // the example only jumps when A, B and C are all holes
+        let example: Script = EXAMPLE.parse().unwrap();
+        assert_eq!(example.jumps(0b1000), Ok(true));
+        assert_eq!(example.jumps(0b1010), Ok(false));
+    }
+}
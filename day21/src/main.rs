@@ -1,3 +1,47 @@
+use std::env;
+use std::fs;
+
+use intcode::Program;
+
+use day21::droid::{self, Report};
+use day21::{survey, Day21, Mode, Script};
+
 fn main() {
-    println!("Hello, world!");
+    // `day21 --script FILE` sends a hand written script instead, and
+    // `day21 --verbose` shows the hulls found and the scripts which worked
+    let mut verbose = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--script" => {
+                let input = aoc::read_input(21).unwrap();
+                let program: Program = input.parse().unwrap_or_else(|e| panic!("{}", e));
+                let script: Script = fs::read_to_string(args.next().unwrap())
+                    .unwrap()
+                    .parse()
+                    .unwrap_or_else(|e| panic!("{}", e));
+                match droid::send(&program, &script).unwrap_or_else(|e| panic!("{}", e)) {
+                    Report::Damage(damage) => println!("{}", damage),
+                    Report::Fell(hull) => println!("fell through {}", hull),
+                }
+                return;
+            }
+            "--verbose" => verbose = true,
+            _ => panic!("unexpected argument {}", arg),
+        }
+    }
+
+    if !verbose {
+        aoc::solve(21, &Day21);
+        return;
+    }
+    let program: Program = aoc::read_input(21)
+        .unwrap()
+        .parse()
+        .unwrap_or_else(|e| panic!("{}", e));
+    for &mode in [Mode::Walk, Mode::Run].iter() {
+        let damage =
+            survey(&program, mode, |x| print!("{}", x)).unwrap_or_else(|e| panic!("{}", e));
+        println!("{}", damage);
+    }
 }
@@ -0,0 +1,402 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::hull::{Hull, JUMP};
+use crate::Instruction::{And, Not, Or};
+use crate::{Instruction, Mode, Register, Script, LIMIT};
+
+// the most sensors looked at together in one clause
+const CLAUSE: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Literal {
+    pub negated: bool,
+    pub sensor: u8,
+}
+
+impl Literal {
+    fn holds(&self, sensors: u16) -> bool {
+        (sensors >> self.sensor & 1 == 1) != self.negated
+    }
+}
+
+// the droid jumps when every clause has at least one literal which holds,
+// so no clauses at all means always jumping
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cnf {
+    pub clauses: Vec<Vec<Literal>>,
+}
+
+impl Cnf {
+    pub fn jumps(&self, sensors: u16) -> bool {
+        self.clauses
+            .iter()
+            .all(|clause| clause.iter().any(|x| x.holds(sensors)))
+    }
+
+    pub fn compile(&self, mode: Mode) -> Script {
+        let mut instructions = vec![];
+        for (i, clause) in self.clauses.iter().enumerate() {
+            if i == 0 {
+                instructions.extend(first(clause));
+            } else {
+                instructions.extend(later(clause));
+            }
+        }
+        if self.clauses.is_empty() {
+            instructions.push(Not(Register::J, Register::J));
+        }
+        Script::new(instructions, mode)
+    }
+}
+
+// the first clause is worked out in J, which is still false
+fn first(clause: &[Literal]) -> Vec<Instruction> {
+    disjunction(clause, Register::J, true)
+}
+
+// and each after that in T, then anded into J
+fn later(clause: &[Literal]) -> Vec<Instruction> {
+    match clause {
+        [x] if !x.negated => vec![And(Register::Sensor(x.sensor), Register::J)],
+        _ => {
+            let mut instructions = disjunction(clause, Register::T, false);
+            instructions.push(And(Register::T, Register::J));
+            instructions
+        }
+    }
+}
+
+// or the literals together into a register, which may or may not still
+// be false from the start, trying the negated ones first and last
+fn disjunction(clause: &[Literal], register: Register, fresh: bool) -> Vec<Instruction> {
+    let mut clause = clause.to_vec();
+    clause.sort();
+    let first = build(&clause, register, fresh);
+    clause.reverse();
+    let last = build(&clause, register, fresh);
+    if last.len() < first.len() {
+        last
+    } else {
+        first
+    }
+}
+
+// the register holds either the clause so far or its negation, whichever
+// is cheaper to extend with the next literal
+fn build(clause: &[Literal], r: Register, fresh: bool) -> Vec<Instruction> {
+    let mut instructions = vec![];
+    let mut negated = false;
+    for (i, literal) in clause.iter().enumerate() {
+        let x = Register::Sensor(literal.sensor);
+        match (i, negated, literal.negated) {
+            (0, _, false) if fresh => instructions.push(Or(x, r)),
+            (0, _, _) => {
+                instructions.push(Not(x, r));
+                negated = !literal.negated;
+            }
+            (_, false, false) => instructions.push(Or(x, r)),
+            (_, true, true) => instructions.push(And(x, r)),
+            (_, false, true) => {
+                instructions.extend(vec![Not(r, r), And(x, r)]);
+                negated = true;
+            }
+            (_, true, false) => {
+                instructions.extend(vec![Not(r, r), Or(x, r)]);
+                negated = false;
+            }
+        }
+    }
+    if negated {
+        instructions.push(Not(r, r));
+    }
+    instructions
+}
+
+// every clause of up to CLAUSE literals on the sensors for the mode,
+// leaving out those which always hold
+fn clauses(mode: Mode) -> Vec<Vec<Literal>> {
+    let literals: Vec<Literal> = (0..mode.sensors())
+        .flat_map(|sensor| {
+            vec![
+                Literal {
+                    negated: false,
+                    sensor,
+                },
+                Literal {
+                    negated: true,
+                    sensor,
+                },
+            ]
+        })
+        .collect();
+
+    let mut clauses: Vec<Vec<Literal>> = vec![vec![]];
+    let mut all = vec![];
+    for _ in 0..CLAUSE {
+        let mut longer = vec![];
+        for clause in clauses.iter() {
+            let last = clause.last().map_or(0, |x| x.sensor + 1);
+            for literal in literals.iter().filter(|x| x.sensor >= last) {
+                let mut clause = clause.clone();
+                clause.push(*literal);
+                longer.push(clause);
+            }
+        }
+        all.extend(longer.iter().cloned());
+        clauses = longer;
+    }
+    all
+}
+
+// which of the sensor readings seen so far the droid jumps on
+type Set = [u64; 8];
+
+// the state before, and the clause anded in after it
+type Step<'a> = (usize, &'a [Literal]);
+
+fn contains(set: &Set, i: usize) -> bool {
+    set[i / 64] >> (i % 64) & 1 == 1
+}
+
+// the cheapest script made of anded clauses which gets the droid across
+// every one of the hulls. only the sensor readings the droid could have
+// on the hulls matter, so scripts which jump on the same ones of those
+// are treated as the same
+pub fn synthesise(hulls: &[Hull], mode: Mode) -> Option<Script> {
+    let mask = (1 << mode.sensors()) - 1;
+    let mut readings: HashMap<u16, usize> = HashMap::new();
+    // the reading at each position along each hull
+    let seen: Vec<Vec<usize>> = hulls
+        .iter()
+        .map(|hull| {
+            (0..hull.len())
+                .map(|position| {
+                    let n = readings.len();
+                    *readings.entry(hull.sensors(position) & mask).or_insert(n)
+                })
+                .collect()
+        })
+        .collect();
+
+    // the readings the droid jumped on before falling in, if it did
+    let falls = |set: &Set| -> Option<Vec<usize>> {
+        hulls
+            .iter()
+            .zip(seen.iter())
+            .find_map(|(hull, seen)| crosses(hull, seen, set).err())
+    };
+    // anding in more clauses only ever takes jumps away, so there's no
+    // point going on once a hull can't be crossed even choosing freely
+    // where to jump among those left
+    let possible = |set: &Set| {
+        hulls.iter().zip(seen.iter()).all(|(hull, seen)| {
+            let mut reached = vec![false; hull.len() + JUMP];
+            reached[0] = true;
+            for position in 0..hull.len() {
+                if reached[position] && hull.is_ground(position) {
+                    reached[position + 1] = true;
+                    reached[position + JUMP] |= contains(set, seen[position]);
+                }
+            }
+            reached[hull.len()..].iter().any(|&x| x)
+        })
+    };
+
+    // the clauses which jump on different readings, and the cheapest way
+    // of doing each as the first clause and as one after that
+    let mut firsts: HashMap<Set, (usize, Vec<Literal>)> = HashMap::new();
+    let mut laters: HashMap<Set, (usize, Vec<Literal>)> = HashMap::new();
+    for clause in clauses(mode) {
+        let mut jumps = [0; 8];
+        for (&sensors, &i) in readings.iter() {
+            if clause.iter().any(|x| x.holds(sensors)) {
+                jumps[i / 64] |= 1 << (i % 64);
+            }
+        }
+        for (options, cost) in [
+            (&mut firsts, first(&clause).len()),
+            (&mut laters, later(&clause).len()),
+        ] {
+            if options.get(&jumps).is_none_or(|x| cost < x.0) {
+                options.insert(jumps, (cost, clause.clone()));
+            }
+        }
+    }
+    let firsts: Vec<(Set, (usize, Vec<Literal>))> = firsts.into_iter().collect();
+    let laters: Vec<(Set, (usize, Vec<Literal>))> = laters.into_iter().collect();
+
+    // no clauses means always jumping, and the way back to the start
+    // from each state after that
+    let mut states: Vec<(Set, Option<Step>)> = vec![([!0; 8], None)];
+    let mut best: HashMap<Set, usize> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((1, 0)));
+
+    while let Some(Reverse((cost, i))) = queue.pop() {
+        let (jumps, _) = states[i];
+        if best.get(&jumps).is_some_and(|&x| x < cost) {
+            continue;
+        }
+        let Some(fell) = falls(&jumps) else {
+            let mut cnf = Cnf::default();
+            let mut i = i;
+            while let (_, Some((parent, clause))) = states[i] {
+                cnf.clauses.insert(0, clause.to_vec());
+                i = parent;
+            }
+            return Some(cnf.compile(mode));
+        };
+
+        let (options, base) = if i == 0 {
+            (&firsts, 0)
+        } else {
+            (&laters, cost)
+        };
+        for (option, (extra, clause)) in options.iter() {
+            let mut next = jumps;
+            for (x, y) in next.iter_mut().zip(option.iter()) {
+                *x &= y;
+            }
+            // only taking away one of the jumps on the way to the hole
+            // can stop the droid falling in it, and any clause which does
+            // that could just as well be the next one
+            if i != 0 && fell.iter().all(|&x| contains(&next, x)) {
+                continue;
+            }
+            let cost = base + extra;
+            if next == jumps || cost > LIMIT || best.get(&next).is_some_and(|&x| x <= cost) {
+                continue;
+            }
+            if possible(&next) {
+                best.insert(next, cost);
+                states.push((next, Some((i, clause))));
+                queue.push(Reverse((cost, states.len() - 1)));
+            }
+        }
+    }
+    None
+}
+
+// the droid's way across, jumping on the readings in the set, or the
+// readings it jumped on if it falls in a hole
+fn crosses(hull: &Hull, seen: &[usize], set: &Set) -> Result<(), Vec<usize>> {
+    let mut jumped = vec![];
+    let mut position = 0;
+    while position < hull.len() {
+        if !hull.is_ground(position) {
+            return Err(jumped);
+        }
+        if contains(set, seen[position]) {
+            jumped.push(seen[position]);
+            position += JUMP;
+        } else {
+            position += 1;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_compile() {
+        use super::{Cnf, Literal};
+        use crate::Mode;
+
+        let literal = |s: &str| Literal {
+            negated: s.starts_with('!'),
+            sensor: s.trim_start_matches('!').as_bytes()[0] - b'A',
+        };
+        for clauses in [
+            vec![],
+            vec![vec!["D"]],
+            vec![vec!["!A"]],
+            vec![vec!["!A", "!B", "!C"], vec!["D"]],
+            vec![vec!["!A", "B", "!C"], vec!["!D", "E"], vec!["!H"]],
+            vec![
+                vec!["A"],
+                vec!["B", "C", "!D"],
+                vec!["!E", "F", "!G"],
+                vec!["H", "I"],
+            ],
+            vec![
+                vec!["!I"],
+                vec!["!A", "!B"],
+                vec!["C"],
+                vec!["!D", "!E", "!F"],
+            ],
+        ] {
+            let cnf = Cnf {
+                clauses: clauses
+                    .iter()
+                    .map(|x| x.iter().map(|&x| literal(x)).collect())
+                    .collect(),
+            };
+            let script = cnf.compile(Mode::Run);
+            for sensors in 0..1 << 9 {
+                assert_eq!(
+                    script.jumps(sensors),
+                    Ok(cnf.jumps(sensors)),
+                    "{:?}",
+                    clauses
+                );
+            }
+        }
+
+        // the usual way of walking across
+        let cnf = Cnf {
+            clauses: vec![
+                vec![literal("!A"), literal("!B"), literal("!C")],
+                vec![literal("D")],
+            ],
+        };
+        assert_eq!(cnf.compile(Mode::Walk).instructions.len(), 6);
+    }
+
+    #[test]
+    fn test_clauses() {
+        use super::clauses;
+        use crate::Mode;
+
+        // 8 single literals, 24 pairs and 32 triples of different sensors
+        assert_eq!(clauses(Mode::Walk).len(), 8 + 24 + 32);
+        assert!(clauses(Mode::Run).iter().all(|x| x.len() <= 3));
+    }
+
+    #[test]
+    fn test_synthesise() {
+        use super::synthesise;
+        use crate::hull::Hull;
+        use crate::Mode;
+
+        let hulls: Vec<Hull> = vec![
+            "#####.###########",
+            "#####..#.########",
+            "#####...#########",
+            "#####.#..########",
+            "#####.##.########",
+        ]
+        .into_iter()
+        .map(|x| x.parse().unwrap())
+        .collect();
+
+        // with nothing to go on, any script will do
+        assert_eq!(synthesise(&[], Mode::Walk).unwrap().instructions.len(), 1);
+
+        for n in 1..=hulls.len() {
+            let script = synthesise(&hulls[..n], Mode::Walk).unwrap();
+            assert!(script.encode().is_ok());
+            for hull in hulls[..n].iter() {
+                assert_eq!(hull.survives(&script), Ok(Ok(())), "{}\n{}", hull, script);
+            }
+        }
+
+        // the droid has to look further ahead than it can while walking
+        let hull: Hull = "#####.#.##..#.####".parse().unwrap();
+        let both = vec![hulls[3].clone(), hull];
+        let script = synthesise(&both, Mode::Run).unwrap();
+        assert!(both.iter().all(|x| x.survives(&script) == Ok(Ok(()))));
+    }
+}
@@ -0,0 +1,215 @@
+use crate::Shuffle;
+
+// every technique moves the card at position p to position a * p + b
+// (mod the size of the deck), and so does any sequence of them
+//
+// deck sizes fit in a u64, so products of two positions fit in a u128
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Affine {
+    pub a: u64,
+    pub b: u64,
+    pub size: u64,
+}
+
+fn mul(x: u64, y: u64, size: u64) -> u64 {
+    ((x as u128 * y as u128) % size as u128) as u64
+}
+
+fn add(x: u64, y: u64, size: u64) -> u64 {
+    ((x as u128 + y as u128) % size as u128) as u64
+}
+
+// the inverse of x mod size, if there is one
+fn mod_inverse(x: u64, size: u64) -> Option<u64> {
+    let (mut r0, mut r1) = (size as i128, x as i128);
+    let (mut t0, mut t1) = (0_i128, 1_i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    if r0 != 1 {
+        return None;
+    }
+    Some(t0.rem_euclid(size as i128) as u64)
+}
+
+impl Affine {
+    pub fn identity(size: u64) -> Self {
+        assert!(size > 0, "a deck needs at least one card");
+        Affine {
+            a: 1 % size,
+            b: 0,
+            size,
+        }
+    }
+
+    pub fn new(shuffle: Shuffle, size: u64) -> Self {
+        assert!(size > 0, "a deck needs at least one card");
+        let (a, b) = match shuffle {
+            // p -> -p - 1
+            Shuffle::IntoNewStack => (size - 1, size - 1),
+            // p -> p - n
+            Shuffle::Cut(n) => (1, (-(n as i128)).rem_euclid(size as i128) as u64),
+            // p -> n * p
+            Shuffle::WithIncrement(n) => ((n as u64) % size, 0),
+        };
+        Affine {
+            a: a % size,
+            b: b % size,
+            size,
+        }
+    }
+
+    // the whole sequence of shuffles as a single map
+    pub fn from_shuffles(shuffles: &[Shuffle], size: u64) -> Self {
+        shuffles.iter().fold(Affine::identity(size), |f, &s| {
+            f.then(&Affine::new(s, size))
+        })
+    }
+
+    // self followed by other
+    pub fn then(&self, other: &Affine) -> Self {
+        assert_eq!(self.size, other.size, "can't mix up different sized decks");
+        Affine {
+            a: mul(other.a, self.a, self.size),
+            b: add(mul(other.a, self.b, self.size), other.b, self.size),
+            size: self.size,
+        }
+    }
+
+    // the same shuffle done the given number of times, by repeated squaring
+    pub fn pow(&self, times: u64) -> Self {
+        let mut result = Affine::identity(self.size);
+        let mut square = *self;
+        let mut times = times;
+        while times > 0 {
+            if times & 1 == 1 {
+                result = result.then(&square);
+            }
+            square = square.then(&square);
+            times >>= 1;
+        }
+        result
+    }
+
+    // undoes the shuffle - only possible when every card ends up in a
+    // different place, i.e. a is coprime to the size of the deck
+    pub fn inverse(&self) -> Option<Self> {
+        let a = mod_inverse(self.a, self.size)?;
+        Some(Affine {
+            a,
+            b: mul(a, self.size - self.b, self.size),
+            size: self.size,
+        })
+    }
+
+    // where the card at the given position ends up
+    pub fn apply(&self, position: u64) -> u64 {
+        add(mul(self.a, position, self.size), self.b, self.size)
+    }
+
+    // which card ends up at the given position, if exactly one does
+    pub fn card_at(&self, position: u64) -> Option<u64> {
+        Some(self.inverse()?.apply(position))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Shuffle;
+
+    fn example() -> Vec<Shuffle> {
+        vec![
+            Shuffle::IntoNewStack,
+            Shuffle::Cut(-2),
+            Shuffle::WithIncrement(7),
+            Shuffle::Cut(8),
+            Shuffle::Cut(-4),
+            Shuffle::WithIncrement(7),
+            Shuffle::Cut(3),
+            Shuffle::WithIncrement(9),
+            Shuffle::WithIncrement(3),
+            Shuffle::Cut(-1),
+        ]
+    }
+
+    #[test]
+    fn test_single_techniques() {
+        use super::Affine;
+        use crate::Deck;
+
+        for shuffle in [
+            Shuffle::IntoNewStack,
+            Shuffle::Cut(3),
+            Shuffle::Cut(-4),
+            Shuffle::Cut(0),
+            Shuffle::WithIncrement(3),
+            Shuffle::WithIncrement(7),
+        ] {
+            let mut deck = Deck::new(10);
+            deck.shuffle(&[shuffle]);
+            let f = Affine::new(shuffle, 10);
+            for card in 0..10 {
+                assert_eq!(f.apply(card as u64) as usize, deck.position(card).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_shuffles() {
+        use super::Affine;
+        use crate::Deck;
+
+        let f = Affine::from_shuffles(&example(), 10);
+        let cards: Vec<usize> = (0..10).map(|p| f.card_at(p).unwrap() as usize).collect();
+        assert_eq!(cards, vec![9, 2, 5, 8, 1, 4, 7, 0, 3, 6]);
+
+        // any prime sized deck works with any increment
+        for size in [11, 13, 17, 101, 10007] {
+            let mut deck = Deck::new(size);
+            deck.shuffle(&example());
+            let f = Affine::from_shuffles(&example(), size as u64);
+            for position in 0..size {
+                assert_eq!(
+                    f.card_at(position as u64),
+                    Some(deck.cards()[position] as u64)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pow() {
+        use super::Affine;
+        use crate::Deck;
+
+        let size = 13;
+        let f = Affine::from_shuffles(&example(), size as u64);
+        let mut deck = Deck::new(size);
+        for times in 0..30 {
+            assert_eq!(f.pow(times).card_at(5), Some(deck.cards()[5] as u64));
+            deck.shuffle(&example());
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        use super::Affine;
+
+        let size = 119315717514047;
+        let f = Affine::from_shuffles(&example(), size).pow(101741582076661);
+        let g = f.inverse().unwrap();
+        assert_eq!(f.then(&g), Affine::identity(size));
+        assert_eq!(g.then(&f), Affine::identity(size));
+        assert_eq!(g.apply(f.apply(2020)), 2020);
+
+        // dealing with an increment of 2 into an even deck stacks some
+        // cards on top of each other, so there's no undoing it
+        assert_eq!(Affine::new(Shuffle::WithIncrement(2), 10).inverse(), None);
+        assert_eq!(
+            Affine::new(Shuffle::WithIncrement(3), 10).card_at(9),
+            Some(3)
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use std::error::Error;
+
+use aoc::{Answer, Solution};
+
+pub mod affine;
+pub mod technique;
+
+pub use affine::Affine;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shuffle {
+    IntoNewStack,
+    WithIncrement(usize),
+    Cut(isize),
+}
+
+pub struct Deck {
+    cards: Vec<usize>,
+}
+
+impl Deck {
+    pub fn new(size: usize) -> Self {
+        Deck {
+            cards: (0..size).collect(),
+        }
+    }
+
+    pub fn deal_into_new_stack(&mut self) {
+        self.cards.reverse();
+    }
+
+    pub fn deal_with_increment(&mut self, size: usize) {
+        let length = self.cards.len();
+        let mut shuffled = vec![0; length];
+
+        for (i, n) in self.cards.iter().enumerate() {
+            shuffled[(i * size) % length] = *n;
+        }
+
+        self.cards = shuffled;
+    }
+
+    // cutting more cards than are in the deck wraps around
+    pub fn cut(&mut self, size: isize) {
+        if !self.cards.is_empty() {
+            let size = size.rem_euclid(self.cards.len() as isize) as usize;
+            self.cards.rotate_left(size)
+        }
+    }
+
+    pub fn cards(&self) -> &[usize] {
+        &self.cards
+    }
+
+    pub fn position(&self, card: usize) -> Option<usize> {
+        self.cards.iter().position(|&x| x == card)
+    }
+
+    pub fn shuffle(&mut self, steps: &[Shuffle]) {
+        for step in steps {
+            match step {
+                Shuffle::IntoNewStack => self.deal_into_new_stack(),
+                Shuffle::Cut(size) => self.cut(*size),
+                Shuffle::WithIncrement(size) => self.deal_with_increment(*size),
+            }
+        }
+    }
+}
+
+// follows a single card through the shuffles one at a time
+pub fn position_after_shuffles(position: usize, card_count: usize, shuffle: &[Shuffle]) -> usize {
+    let mut p = position;
+    for s in shuffle {
+        p = match s {
+            Shuffle::IntoNewStack => card_count - p - 1,
+            Shuffle::Cut(size) => {
+                (p + card_count - size.rem_euclid(card_count as isize) as usize) % card_count
+            }
+            Shuffle::WithIncrement(size) => (p * size) % card_count,
+        }
+    }
+    p
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Vec<Shuffle>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Shuffle>, Box<dyn Error>> {
+        Ok(technique::parse(input)?)
+    }
+
+    // where card 2019 ends up in a deck of 10007
+    fn part1(&self, steps: &Vec<Shuffle>) -> Answer {
+        Ok(Affine::from_shuffles(steps, 10007).apply(2019).to_string())
+    }
+
+    // the card which ends up at 2020 after shuffling a huge deck a huge
+    // number of times
+    fn part2(&self, steps: &Vec<Shuffle>) -> Answer {
+        let card = Affine::from_shuffles(steps, 119315717514047)
+            .pow(101741582076661)
+            .card_at(2020)
+            .ok_or("the shuffle can't be undone")?;
+        Ok(card.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_deal_into_stack() {
+        use super::Deck;
+
+        let mut deck = Deck::new(10);
+        deck.deal_into_new_stack();
+        assert_eq!(deck.cards, (0..10).rev().collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_deal_with_increment() {
+        use super::Deck;
+
+        let mut deck = Deck::new(10);
+        deck.deal_with_increment(3);
+
+        assert_eq!(deck.cards, vec![0, 7, 4, 1, 8, 5, 2, 9, 6, 3])
+    }
+
+    #[test]
+    fn test_cut() {
+        use super::Deck;
+
+        let mut deck = Deck::new(10);
+        deck.cut(3);
+
+        assert_eq!(deck.cards, vec![3, 4, 5, 6, 7, 8, 9, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_cut_negative() {
+        use super::Deck;
+
+        let mut deck = Deck::new(10);
+        deck.cut(-4);
+
+        assert_eq!(deck.cards, vec![6, 7, 8, 9, 0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_example1() {
+        use super::Deck;
+
+        let mut deck = Deck::new(10);
+        deck.deal_with_increment(7);
+        deck.deal_into_new_stack();
+        deck.deal_into_new_stack();
+
+        assert_eq!(deck.cards, vec![0, 3, 6, 9, 2, 5, 8, 1, 4, 7]);
+    }
+
+    #[test]
+    fn test_example2() {
+        use super::Deck;
+
+        let mut deck = Deck::new(10);
+        deck.cut(6);
+        deck.deal_with_increment(7);
+        deck.deal_into_new_stack();
+
+        assert_eq!(deck.cards, vec![3, 0, 7, 4, 1, 8, 5, 2, 9, 6]);
+    }
+
+    #[test]
+    fn test_example3() {
+        use super::Deck;
+
+        let mut deck = Deck::new(10);
+        deck.deal_with_increment(7);
+        deck.deal_with_increment(9);
+        deck.cut(-2);
+
+        assert_eq!(deck.cards, vec![6, 3, 0, 7, 4, 1, 8, 5, 2, 9]);
+    }
+
+    #[test]
+    fn test_example4() {
+        use super::Deck;
+
+        let mut deck = Deck::new(10);
+        deck.deal_into_new_stack();
+        deck.cut(-2);
+        deck.deal_with_increment(7);
+        deck.cut(8);
+        deck.cut(-4);
+        deck.deal_with_increment(7);
+        deck.cut(3);
+        deck.deal_with_increment(9);
+        deck.deal_with_increment(3);
+        deck.cut(-1);
+
+        assert_eq!(deck.cards, vec![9, 2, 5, 8, 1, 4, 7, 0, 3, 6]);
+    }
+
+    #[test]
+    fn test_position_after_shuffles() {
+        use super::{position_after_shuffles, Deck, Shuffle};
+
+        let steps = vec![
+            Shuffle::IntoNewStack,
+            Shuffle::Cut(-2),
+            Shuffle::WithIncrement(7),
+            Shuffle::Cut(8),
+            Shuffle::Cut(-4),
+            Shuffle::WithIncrement(7),
+            Shuffle::Cut(3),
+            Shuffle::WithIncrement(9),
+            Shuffle::WithIncrement(3),
+            Shuffle::Cut(-1),
+        ];
+        let mut deck = Deck::new(10);
+        deck.shuffle(&steps);
+        for card in 0..10 {
+            assert_eq!(
+                Some(position_after_shuffles(card, 10, &steps)),
+                deck.position(card)
+            );
+        }
+    }
+}
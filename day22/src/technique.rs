@@ -0,0 +1,227 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Affine, Deck, Shuffle};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+impl FromStr for Shuffle {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let line = line.trim();
+        if line == "deal into new stack" {
+            Ok(Shuffle::IntoNewStack)
+        } else if let Some(n) = line.strip_prefix("cut ") {
+            n.trim()
+                .parse()
+                .map(Shuffle::Cut)
+                .map_err(|_| format!("can't cut {:?} cards", n))
+        } else if let Some(n) = line.strip_prefix("deal with increment ") {
+            match n.trim().parse() {
+                Ok(0) => Err("can't deal with an increment of 0".to_string()),
+                Ok(n) => Ok(Shuffle::WithIncrement(n)),
+                Err(_) => Err(format!("can't deal with an increment of {:?}", n)),
+            }
+        } else {
+            Err(format!("unknown technique {:?}", line))
+        }
+    }
+}
+
+impl fmt::Display for Shuffle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Shuffle::IntoNewStack => write!(f, "deal into new stack"),
+            Shuffle::Cut(n) => write!(f, "cut {}", n),
+            Shuffle::WithIncrement(n) => write!(f, "deal with increment {}", n),
+        }
+    }
+}
+
+// one technique per line, ignoring blank lines
+pub fn parse(input: &str) -> Result<Vec<Shuffle>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.parse().map_err(|message| ParseError {
+                line: i + 1,
+                message,
+            })
+        })
+        .collect()
+}
+
+// the inverse of parse
+pub fn format(shuffles: &[Shuffle]) -> String {
+    shuffles.iter().map(|s| format!("{}\n", s)).collect()
+}
+
+// an equivalent sequence for the given size of deck, in the form
+//
+//   deal with increment a
+//   cut c
+//   deal into new stack (only if that makes the increment smaller)
+//
+// with the cut as close to 0 as possible, and any steps which would
+// leave the deck as it is left out
+pub fn optimise(shuffles: &[Shuffle], size: u64) -> Vec<Shuffle> {
+    let f = Affine::from_shuffles(shuffles, size);
+
+    // p -> a p - c, or with the new stack p -> -((size - a) p - c) - 1,
+    // which is a p + c - 1
+    let reverse = f.a > size / 2;
+    let (increment, cut) = if reverse {
+        (size - f.a, (f.b + 1) % size)
+    } else {
+        (f.a, (size - f.b) % size)
+    };
+    let cut = if cut > size / 2 {
+        -((size - cut) as i128)
+    } else {
+        cut as i128
+    };
+
+    let mut steps = vec![
+        Shuffle::WithIncrement(increment as usize),
+        Shuffle::Cut(cut as isize),
+    ];
+    if reverse {
+        steps.push(Shuffle::IntoNewStack);
+    }
+    steps.retain(|&s| Affine::new(s, size) != Affine::identity(size));
+    steps
+}
+
+// whether the two sequences leave a deck of the given size in the
+// same order, by actually shuffling it
+pub fn equivalent(a: &[Shuffle], b: &[Shuffle], size: usize) -> bool {
+    let mut first = Deck::new(size);
+    first.shuffle(a);
+    let mut second = Deck::new(size);
+    second.shuffle(b);
+    first.cards() == second.cards()
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE: &str = "deal into new stack
+cut -2
+deal with increment 7
+cut 8
+cut -4
+deal with increment 7
+cut 3
+deal with increment 9
+deal with increment 3
+cut -1
+";
+
+    #[test]
+    fn test_parse() {
+        use super::parse;
+        use crate::{Deck, Shuffle};
+
+        let steps = parse(EXAMPLE).unwrap();
+        assert_eq!(steps.len(), 10);
+        assert_eq!(steps[0], Shuffle::IntoNewStack);
+        assert_eq!(steps[1], Shuffle::Cut(-2));
+        assert_eq!(steps[2], Shuffle::WithIncrement(7));
+
+        let mut deck = Deck::new(10);
+        deck.shuffle(&steps);
+        assert_eq!(deck.cards(), &[9, 2, 5, 8, 1, 4, 7, 0, 3, 6]);
+
+        assert_eq!(parse("\n  cut 3  \n\n"), Ok(vec![Shuffle::Cut(3)]));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use super::{parse, ParseError};
+
+        assert_eq!(
+            parse("cut 1\n\ndeal with increment -3"),
+            Err(ParseError {
+                line: 3,
+                message: "can't deal with an increment of \"-3\"".to_string()
+            })
+        );
+        assert_eq!(
+            parse("deal with increment 0").unwrap_err().to_string(),
+            "line 1: can't deal with an increment of 0"
+        );
+        assert_eq!(
+            parse("cut 1\ncut x").unwrap_err().to_string(),
+            "line 2: can't cut \"x\" cards"
+        );
+        assert_eq!(
+            parse("shuffle").unwrap_err().to_string(),
+            "line 1: unknown technique \"shuffle\""
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        use super::{format, parse};
+
+        assert_eq!(format(&parse(EXAMPLE).unwrap()), EXAMPLE);
+        assert_eq!(format(&[]), "");
+    }
+
+    #[test]
+    fn test_optimise() {
+        use super::{equivalent, optimise, parse};
+        use crate::Shuffle;
+
+        let steps = parse(EXAMPLE).unwrap();
+        for size in [10, 11, 13, 17, 101] {
+            let optimised = optimise(&steps, size as u64);
+            assert!(optimised.len() <= 3);
+            assert!(equivalent(&steps, &optimised, size));
+        }
+
+        // one of each, shuffled the other way around
+        let steps = parse("deal into new stack\ncut 3\ndeal with increment 3").unwrap();
+        assert_eq!(
+            optimise(&steps, 10),
+            vec![
+                Shuffle::WithIncrement(3),
+                Shuffle::Cut(-1),
+                Shuffle::IntoNewStack
+            ]
+        );
+        assert!(equivalent(&steps, &optimise(&steps, 10), 10));
+    }
+
+    #[test]
+    fn test_optimise_no_ops() {
+        use super::{optimise, parse};
+        use crate::Shuffle;
+
+        // reversing twice, or cutting the whole deck, does nothing
+        let steps = parse("deal into new stack\ndeal into new stack\ncut 10").unwrap();
+        assert_eq!(optimise(&steps, 10), vec![]);
+        assert_eq!(optimise(&[], 10), vec![]);
+        assert_eq!(optimise(&[Shuffle::Cut(-3)], 10), vec![Shuffle::Cut(-3)]);
+        assert_eq!(
+            optimise(&[Shuffle::IntoNewStack], 10),
+            vec![Shuffle::IntoNewStack]
+        );
+    }
+}
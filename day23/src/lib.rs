@@ -0,0 +1,205 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::mem;
+
+use aoc::{Answer, Solution};
+use intcode::{Interupt, Program};
+
+pub const COMPUTERS: usize = 50;
+// the address of the NAT, which watches over the network
+pub const NAT: usize = 255;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Packet {
+    pub destination: usize,
+    pub x: isize,
+    pub y: isize,
+}
+
+// a computer on the network, which stops whenever it's out of input
+struct Nic {
+    state: Interupt,
+    // anything output since the last whole packet
+    sent: Vec<isize>,
+}
+
+impl Nic {
+    fn new(program: Program) -> Self {
+        Nic {
+            state: program.execute(),
+            sent: vec![],
+        }
+    }
+
+    // feeds the program the input, giving back the packets it sent before
+    // it wanted more or halted
+    fn run(&mut self, input: &[isize]) -> Vec<Packet> {
+        let mut state = mem::replace(&mut self.state, Interupt::Halt);
+        let mut input = input.iter();
+        let mut packets = vec![];
+        loop {
+            state = match state {
+                Interupt::Halt => return packets,
+                Interupt::Input(waiting) => match input.next() {
+                    Some(&value) => waiting.input(value).execute(),
+                    None => {
+                        self.state = Interupt::Input(waiting);
+                        return packets;
+                    }
+                },
+                Interupt::Output(waiting) => {
+                    let (program, value) = waiting.receive();
+                    self.sent.push(value);
+                    if let [destination, x, y] = self.sent[..] {
+                        packets.push(Packet {
+                            destination: destination as usize,
+                            x,
+                            y,
+                        });
+                        self.sent.clear();
+                    }
+                    program.execute()
+                }
+            };
+        }
+    }
+}
+
+pub struct Network {
+    nics: Vec<Nic>,
+    // the input waiting for each computer
+    queues: Vec<VecDeque<isize>>,
+}
+
+impl Network {
+    pub fn new(program: &Program) -> Self {
+        Network {
+            nics: (0..COMPUTERS).map(|_| Nic::new(program.clone())).collect(),
+            // each computer's told its address first
+            queues: (0..COMPUTERS)
+                .map(|x| VecDeque::from(vec![x as isize]))
+                .collect(),
+        }
+    }
+
+    pub fn send(&mut self, packet: Packet) {
+        self.queues[packet.destination].extend(vec![packet.x, packet.y]);
+    }
+
+    // lets every computer take in its packets, or -1 if it has none, and
+    // gives back everything sent to addresses not on the network
+    pub fn step(&mut self) -> Vec<Packet> {
+        let mut outside = vec![];
+        for i in 0..COMPUTERS {
+            let input: Vec<isize> = if self.queues[i].is_empty() {
+                vec![-1]
+            } else {
+                self.queues[i].drain(..).collect()
+            };
+            for packet in self.nics[i].run(&input) {
+                if packet.destination < COMPUTERS {
+                    self.send(packet);
+                } else {
+                    outside.push(packet);
+                }
+            }
+        }
+        outside
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.queues.iter().all(|x| x.is_empty())
+    }
+}
+
+// the first packet sent to the NAT
+pub fn first_nat(program: &Program) -> Packet {
+    let mut network = Network::new(program);
+    loop {
+        if let Some(&packet) = network.step().iter().find(|x| x.destination == NAT) {
+            return packet;
+        }
+    }
+}
+
+// the NAT keeps the last packet it was sent, and sends it on to address
+// 0 whenever the network goes quiet. this gives the first y it sends
+// twice in a row
+pub fn repeated_wake(program: &Program) -> isize {
+    let mut network = Network::new(program);
+    let mut nat = None;
+    let mut woken = None;
+    loop {
+        let outside = network.step();
+        let quiet = outside.is_empty() && network.is_idle();
+        if let Some(&packet) = outside.iter().rev().find(|x| x.destination == NAT) {
+            nat = Some(packet);
+        }
+        if !quiet {
+            continue;
+        }
+        if let Some(packet) = nat {
+            if woken == Some(packet.y) {
+                return packet.y;
+            }
+            woken = Some(packet.y);
+            network.send(Packet {
+                destination: 0,
+                ..packet
+            });
+        }
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Program;
+
+    fn parse(&self, input: &str) -> Result<Program, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, program: &Program) -> Answer {
+        Ok(first_nat(program).y.to_string())
+    }
+
+    fn part2(&self, program: &Program) -> Answer {
+        Ok(repeated_wake(program).to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    // reads its address, sends (7, address) to the NAT, then waits on
+    // input forever
+    const REPORT: &str = "3,13,104,255,104,7,4,13,3,14,1105,1,8,0,0";
+
+    #[test]
+    fn test_first_nat() {
+        use super::{first_nat, Packet, NAT};
+        use intcode::Program;
+
+        let program: Program = REPORT.parse().unwrap();
+        assert_eq!(
+            first_nat(&program),
+            Packet {
+                destination: NAT,
+                x: 7,
+                y: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_repeated_wake() {
+        use super::repeated_wake;
+        use intcode::Program;
+
+        // the NAT ends up holding the last computer's packet, and nothing
+        // answers when it's passed on
+        let program: Program = REPORT.parse().unwrap();
+        assert_eq!(repeated_wake(&program), 49);
+    }
+}
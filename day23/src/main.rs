@@ -1,3 +1,5 @@
+use day23::Day23;
+
 fn main() {
-    println!("Hello, world!");
+    aoc::solve(23, &Day23);
 }
@@ -0,0 +1,296 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_utils::grid::Grid as Tiles;
+use aoc_utils::{Bounds, YAxis};
+
+use crate::Point;
+
+// a width x height grid of bits, packed row by row into u64s, so the
+// cell (x, y) is bit y * width + x
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+}
+
+impl Grid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Grid {
+            width,
+            height,
+            words: vec![0; (width * height).div_ceil(64)],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        if p.0 < 0 || p.1 < 0 || p.0 as usize >= self.width || p.1 as usize >= self.height {
+            return None;
+        }
+        Some(p.1 as usize * self.width + p.0 as usize)
+    }
+
+    pub fn get(&self, p: Point) -> bool {
+        match self.index(p) {
+            Some(i) => self.words[i / 64] >> (i % 64) & 1 == 1,
+            None => false,
+        }
+    }
+
+    pub fn set(&mut self, p: Point, value: bool) {
+        let i = self.index(p).expect("can't set a cell outside the grid");
+        if value {
+            self.words[i / 64] |= 1 << (i % 64);
+        } else {
+            self.words[i / 64] &= !(1 << (i % 64));
+        }
+    }
+
+    // a grid with only the given cell set
+    pub fn single(width: usize, height: usize, p: Point) -> Self {
+        let mut grid = Grid::new(width, height);
+        grid.set(p, true);
+        grid
+    }
+
+    pub fn row(width: usize, height: usize, y: usize) -> Self {
+        let mut grid = Grid::new(width, height);
+        for x in 0..width {
+            grid.set(Point(x as isize, y as isize), true);
+        }
+        grid
+    }
+
+    pub fn column(width: usize, height: usize, x: usize) -> Self {
+        let mut grid = Grid::new(width, height);
+        for y in 0..height {
+            grid.set(Point(x as isize, y as isize), true);
+        }
+        grid
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|x| x.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&x| x == 0)
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.width * self.height)
+            .filter(move |i| self.words[i / 64] >> (i % 64) & 1 == 1)
+            .map(move |i| Point((i % self.width) as isize, (i / self.width) as isize))
+    }
+
+    // the bits past the last cell of the last word are always kept clear
+    fn trim(mut self) -> Self {
+        let bits = self.width * self.height;
+        if !bits.is_multiple_of(64) {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (bits % 64)) - 1;
+            }
+        }
+        self
+    }
+
+    fn zip(&self, other: &Grid, f: impl Fn(u64, u64) -> u64) -> Self {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "can't combine grids of different sizes"
+        );
+        Grid {
+            width: self.width,
+            height: self.height,
+            words: self
+                .words
+                .iter()
+                .zip(other.words.iter())
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+        }
+    }
+
+    pub fn and(&self, other: &Grid) -> Self {
+        self.zip(other, |a, b| a & b)
+    }
+
+    pub fn or(&self, other: &Grid) -> Self {
+        self.zip(other, |a, b| a | b)
+    }
+
+    pub fn xor(&self, other: &Grid) -> Self {
+        self.zip(other, |a, b| a ^ b)
+    }
+
+    pub fn and_not(&self, other: &Grid) -> Self {
+        self.zip(other, |a, b| a & !b)
+    }
+
+    pub fn not(&self) -> Self {
+        Grid {
+            width: self.width,
+            height: self.height,
+            words: self.words.iter().map(|&x| !x).collect(),
+        }
+        .trim()
+    }
+
+    // moves every bit up by the given number of places (down if negative)
+    fn shift_bits(&self, places: isize) -> Self {
+        let (words, bits) = (places.unsigned_abs() / 64, places.unsigned_abs() % 64);
+        let n = self.words.len();
+        let word = |i: Option<usize>| i.and_then(|i| self.words.get(i)).copied().unwrap_or(0);
+
+        let shifted = (0..n)
+            .map(|i| {
+                if places >= 0 {
+                    let low = if bits > 0 {
+                        word(i.checked_sub(words + 1)) >> (64 - bits)
+                    } else {
+                        0
+                    };
+                    word(i.checked_sub(words)) << bits | low
+                } else {
+                    let high = if bits > 0 {
+                        word(Some(i + words + 1)) << (64 - bits)
+                    } else {
+                        0
+                    };
+                    word(Some(i + words)) >> bits | high
+                }
+            })
+            .collect();
+
+        Grid {
+            width: self.width,
+            height: self.height,
+            words: shifted,
+        }
+        .trim()
+    }
+
+    // the grid moved over by dx (at most one column) and dy rows, with
+    // anything which falls off the edges lost rather than wrapping around
+    pub fn shifted(&self, dx: isize, dy: isize) -> Self {
+        assert!(dx.abs() <= 1, "can only shift by one column at a time");
+        let moved = self.shift_bits(dy * self.width as isize + dx);
+        match dx {
+            1 => moved.and_not(&Grid::column(self.width, self.height, 0)),
+            -1 => moved.and_not(&Grid::column(self.width, self.height, self.width - 1)),
+            _ => moved,
+        }
+    }
+
+    // each cell with a bug counts 2 ^ its index, i.e. the bits as a
+    // number, or None if that's too big for a u64
+    pub fn biodiversity(&self) -> Option<u64> {
+        if self.words.iter().skip(1).any(|&x| x != 0) {
+            return None;
+        }
+        Some(self.words.first().copied().unwrap_or(0))
+    }
+}
+
+impl FromStr for Grid {
+    type Err = String;
+
+    // '#' for a bug, '.' (or '?', for a recursive grid's centre) for none
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = input
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .collect();
+        let tiles = Tiles::parse(&lines.join("\n"), YAxis::Down, |c| match c {
+            '#' => Some(true),
+            '.' | '?' => Some(false),
+            _ => None,
+        })
+        .map_err(|e| e.to_string())?;
+
+        let mut grid = Grid::new(tiles.width(), tiles.height());
+        for (p, &bug) in tiles.iter() {
+            if bug {
+                grid.set(p, true);
+            }
+        }
+        Ok(grid)
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bounds = Bounds::new(self.width, self.height);
+        let text = bounds.render(YAxis::Down, |p| if self.get(p) { '#' } else { '.' });
+        write!(f, "{}", text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_parse() {
+        use super::Grid;
+        use crate::Point;
+
+        let grid: Grid = "....#\n#..#.\n#..##\n..#..\n#....".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (5, 5));
+        assert_eq!(grid.count(), 8);
+        assert!(grid.get(Point(4, 0)) && grid.get(Point(0, 1)) && !grid.get(Point(1, 1)));
+        assert_eq!(grid.to_string(), "....#\n#..#.\n#..##\n..#..\n#....\n");
+
+        assert!("#.\n#".parse::<Grid>().is_err());
+        assert!("#x".parse::<Grid>().is_err());
+    }
+
+    #[test]
+    fn test_biodiversity() {
+        use super::Grid;
+        use crate::Point;
+
+        let grid: Grid = ".....\n.....\n.....\n#....\n.#...".parse().unwrap();
+        assert_eq!(grid.biodiversity(), Some(2129920));
+
+        // bit 64 doesn't fit, but bit 63 does
+        let mut grid = Grid::new(13, 5);
+        grid.set(Point(11, 4), true);
+        assert_eq!(grid.biodiversity(), Some(1 << 63));
+        grid.set(Point(12, 4), true);
+        assert_eq!(grid.biodiversity(), None);
+    }
+
+    #[test]
+    fn test_shifted() {
+        use super::Grid;
+        use crate::Point;
+
+        // big enough to need several words
+        for (width, height) in [(5, 5), (10, 20), (64, 3), (33, 7)] {
+            let mut grid = Grid::new(width, height);
+            for i in 0..width * height {
+                if i % 3 == 0 || i % 7 == 0 {
+                    grid.set(Point((i % width) as isize, (i / width) as isize), true);
+                }
+            }
+            for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 2), (-1, -3), (0, 0)] {
+                let shifted = grid.shifted(dx, dy);
+                for y in 0..height as isize {
+                    for x in 0..width as isize {
+                        assert_eq!(shifted.get(Point(x, y)), grid.get(Point(x - dx, y - dy)));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// the states from `start` onwards repeat every `length` steps, so the
+// first state to be seen twice is the one after `start` steps
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+// both only ever hold a couple of states, rather than every state seen.
+// neither returns if the states never repeat
+
+pub fn brent<T: Clone + Eq>(initial: &T, step: impl Fn(&T) -> T) -> Cycle {
+    // find the length by moving the tortoise up to the hare at each
+    // power of two, until the hare catches it up
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // then with the hare that far ahead, they meet at the start
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+pub fn floyd<T: Clone + Eq>(initial: &T, step: impl Fn(&T) -> T) -> Cycle {
+    // the hare moves twice as fast, so they meet somewhere in the cycle
+    let mut tortoise = step(initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    let mut tortoise = initial.clone();
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_cycles() {
+        use super::{brent, floyd, Cycle};
+
+        // 0, 1, 2, 3, 4, 5, 6, then 3, 4, 5, 6, ...
+        let step = |&x: &usize| if x == 6 { 3 } else { x + 1 };
+        let expected = Cycle {
+            start: 3,
+            length: 4,
+        };
+        assert_eq!(brent(&0, step), expected);
+        assert_eq!(floyd(&0, step), expected);
+
+        // straight back to the start
+        assert_eq!(
+            brent(&0, |&x| x),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+        assert_eq!(
+            floyd(&0, |&x| x),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+
+        let step = |&x: &u64| (x * x + 1) % 255;
+        assert_eq!(brent(&3, step), floyd(&3, step));
+    }
+}
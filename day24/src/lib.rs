@@ -0,0 +1,264 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::{Answer, Solution};
+pub use aoc_utils::Point;
+
+pub mod bitset;
+pub mod cycle;
+pub mod rules;
+pub mod topology;
+
+use bitset::Grid;
+use cycle::Cycle;
+pub use rules::Rules;
+pub use topology::{Flat, Recursive, Topology};
+
+#[derive(Clone)]
+pub struct Layout<T: Topology = Flat> {
+    topology: T,
+    rules: Rules,
+    state: T::State,
+}
+
+impl<T: Topology> Layout<T> {
+    pub fn new(topology: T, grid: &Grid) -> Self {
+        Layout {
+            state: topology.state(grid),
+            topology,
+            rules: Rules::eris(),
+        }
+    }
+
+    pub fn with_rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn state(&self) -> &T::State {
+        &self.state
+    }
+
+    pub fn bugs(&self) -> Vec<T::Cell> {
+        self.topology.bugs(&self.state)
+    }
+
+    pub fn count(&self) -> usize {
+        self.topology.count(&self.state)
+    }
+
+    pub fn iterate(&mut self) {
+        self.state = self.topology.step(&self.state, &self.rules);
+    }
+
+    pub fn iterations(&mut self, minutes: usize) {
+        for _ in 0..minutes {
+            self.iterate();
+        }
+    }
+}
+
+impl Layout<Flat> {
+    pub fn flat(input: &str) -> Result<Self, String> {
+        let grid: Grid = input.parse()?;
+        let size = (grid.width(), grid.height());
+        Ok(Layout::new(Flat { size }, &grid))
+    }
+
+    pub fn biodiversity(&self) -> Option<u64> {
+        self.state.biodiversity()
+    }
+
+    // when the layouts start repeating, and how often. a flat grid only has
+    // so many layouts, so it always ends up repeating
+    pub fn cycle(&self) -> Cycle {
+        cycle::brent(&self.state, |x| self.topology.step(x, &self.rules))
+    }
+
+    // the first layout which appears twice
+    pub fn first_repeat(&self) -> Self {
+        let mut layout = self.clone();
+        layout.iterations(self.cycle().start);
+        layout
+    }
+}
+
+impl Layout<Recursive> {
+    pub fn recursive(input: &str) -> Result<Self, String> {
+        let grid: Grid = input.parse()?;
+        let size = (grid.width(), grid.height());
+        Ok(Layout::new(Recursive::new(size)?, &grid))
+    }
+
+    // from the outermost level with any bugs to the innermost
+    pub fn levels(&self) -> Vec<isize> {
+        self.state.keys().cloned().collect()
+    }
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Layout::flat(input)
+    }
+}
+
+impl fmt::Display for Layout<Flat> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.state)
+    }
+}
+
+// every level with bugs, like the puzzle, with the inner level as a '?'
+impl fmt::Display for Layout<Recursive> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (width, centre) = (self.topology.size.0, self.topology.centre());
+        for (i, (level, grid)) in self.state.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "Depth {}:", level)?;
+            let mut tiles: Vec<char> = grid.to_string().chars().collect();
+            // each row is followed by a newline
+            tiles[centre.1 as usize * (width + 1) + centre.0 as usize] = '?';
+            write!(f, "{}", tiles.into_iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Grid;
+
+    fn parse(&self, input: &str) -> Result<Grid, Box<dyn Error>> {
+        Ok(input.parse::<Grid>()?)
+    }
+
+    // the biodiversity of the first layout to appear twice
+    fn part1(&self, grid: &Grid) -> Answer {
+        let size = (grid.width(), grid.height());
+        let layout = Layout::new(Flat { size }, grid).first_repeat();
+        let biodiversity = layout.biodiversity().ok_or("the grid's too big to score")?;
+        Ok(biodiversity.to_string())
+    }
+
+    // the bugs on every level after 200 minutes of recursive growth
+    fn part2(&self, grid: &Grid) -> Answer {
+        let size = (grid.width(), grid.height());
+        let mut layout = Layout::new(Recursive::new(size)?, grid);
+        layout.iterations(200);
+        Ok(layout.count().to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const EXAMPLE: &str = "....#
+#..#.
+#..##
+..#..
+#....";
+
+    #[test]
+    fn test_iterate() {
+        use super::Layout;
+
+        let mut layout: Layout = EXAMPLE.parse().unwrap();
+        println!("{}", layout);
+        let next: Layout = "#..#.
+####.
+###.#
+##.##
+.##.."
+            .parse()
+            .unwrap();
+        layout.iterate();
+        println!("{}", layout);
+
+        assert_eq!(layout.state, next.state);
+    }
+
+    #[test]
+    fn test_first_repeat() {
+        use super::Layout;
+
+        let layout = Layout::flat(EXAMPLE).unwrap();
+        let cycle = layout.cycle();
+        assert_eq!(
+            cycle,
+            super::cycle::floyd(layout.state(), |x| {
+                let mut next = Layout::new(super::Flat { size: (5, 5) }, x);
+                next.iterate();
+                next.state().clone()
+            })
+        );
+        let repeat = layout.first_repeat();
+        assert_eq!(repeat.to_string(), ".....\n.....\n.....\n#....\n.#...\n");
+        assert_eq!(repeat.biodiversity(), Some(2129920));
+    }
+
+    #[test]
+    fn test_big_grid() {
+        use super::{Flat, Layout, Rules};
+        use crate::bitset::Grid;
+
+        // with bugs appearing next to any other bug, and never dying, they
+        // spread out into a diamond, across plenty of words of bits
+        let mut grid = Grid::new(100, 200);
+        grid.set(super::Point(50, 100), true);
+        let rules: Rules = "B1234/S01234".parse().unwrap();
+        let mut layout = Layout::new(Flat { size: (100, 200) }, &grid).with_rules(rules);
+        layout.iterations(10);
+        assert_eq!(layout.count(), 2 * 10 * 10 + 2 * 10 + 1);
+        assert!(layout
+            .bugs()
+            .iter()
+            .all(|p| (p.0 - 50).abs() + (p.1 - 100).abs() <= 10));
+        assert_eq!(layout.biodiversity(), None);
+        assert_eq!(Rules::eris().to_string(), "B12/S1");
+    }
+
+    #[test]
+    fn test_recursive() {
+        use super::{Day24, Layout};
+        use aoc::Solution;
+
+        let mut layout = Layout::recursive(EXAMPLE).unwrap();
+        layout.iterations(10);
+        assert_eq!(layout.count(), 99);
+        assert_eq!(layout.levels(), (-5..=5).collect::<Vec<isize>>());
+
+        let output = layout.to_string();
+        assert!(output.starts_with(
+            "Depth -5:
+..#..
+.#.#.
+..?.#
+.#.#.
+..#..
+
+Depth -4:
+"
+        ));
+        assert!(output.ends_with(
+            "Depth 5:
+####.
+#..#.
+#.?#.
+####.
+.....
+"
+        ));
+
+        // an even sized grid has no centre tile to recurse into
+        let even = Day24.parse("....\n.#..\n....\n....").unwrap();
+        assert!(Day24.part2(&even).is_err());
+        assert!(Layout::recursive("...\n.?.").is_err());
+        assert!("..#\n.x.".parse::<Layout>().is_err());
+    }
+}
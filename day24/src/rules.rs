@@ -0,0 +1,168 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bitset::Grid;
+
+// how many neighbouring bugs each cell has, as a binary number per cell
+// spread across a grid per bit, so adding a whole grid of 0s and 1s at
+// once is just a few bit operations per word
+#[derive(Clone, Debug)]
+pub struct Counter {
+    planes: Vec<Grid>,
+}
+
+impl Counter {
+    pub fn new() -> Self {
+        Counter { planes: vec![] }
+    }
+
+    pub fn add(&mut self, grid: &Grid) {
+        let mut carry = grid.clone();
+        for plane in self.planes.iter_mut() {
+            if carry.is_empty() {
+                return;
+            }
+            let next = plane.and(&carry);
+            *plane = plane.xor(&carry);
+            carry = next;
+        }
+        if !carry.is_empty() {
+            self.planes.push(carry);
+        }
+    }
+
+    // the cells with exactly the given count, out of the cells in `within`
+    pub fn equals(&self, count: usize, within: &Grid) -> Grid {
+        if count.checked_shr(self.planes.len() as u32).unwrap_or(0) != 0 {
+            return Grid::new(within.width(), within.height());
+        }
+        self.planes
+            .iter()
+            .enumerate()
+            .fold(within.clone(), |cells, (bit, plane)| {
+                if count >> bit & 1 == 1 {
+                    cells.and(plane)
+                } else {
+                    cells.and_not(plane)
+                }
+            })
+    }
+}
+
+impl Default for Counter {
+    fn default() -> Self {
+        Counter::new()
+    }
+}
+
+// which numbers of neighbouring bugs make a bug appear in an empty
+// cell, and which let an existing bug live, written like B12/S1
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    pub birth: Vec<usize>,
+    pub survival: Vec<usize>,
+}
+
+impl Rules {
+    // the puzzle's rules
+    pub fn eris() -> Self {
+        Rules {
+            birth: vec![1, 2],
+            survival: vec![1],
+        }
+    }
+
+    pub fn apply(&self, alive: &Grid, counts: &Counter) -> Grid {
+        let empty = alive.not();
+        let mut next = Grid::new(alive.width(), alive.height());
+        for &count in self.birth.iter() {
+            next = next.or(&counts.equals(count, &empty));
+        }
+        for &count in self.survival.iter() {
+            next = next.or(&counts.equals(count, alive));
+        }
+        next
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::eris()
+    }
+}
+
+impl FromStr for Rules {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let counts = |part: &str, prefix: char| -> Result<Vec<usize>, String> {
+            let digits = part
+                .trim()
+                .strip_prefix(prefix)
+                .ok_or_else(|| format!("expected {} in {:?}", prefix, input))?;
+            digits
+                .chars()
+                .map(|c| {
+                    c.to_digit(10)
+                        .map(|x| x as usize)
+                        .ok_or_else(|| format!("unexpected {:?} in {:?}", c, input))
+                })
+                .collect()
+        };
+        match input.split_once('/') {
+            Some((birth, survival)) => Ok(Rules {
+                birth: counts(birth, 'B')?,
+                survival: counts(survival, 'S')?,
+            }),
+            None => Err(format!("expected B.../S... but got {:?}", input)),
+        }
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |x: &[usize]| x.iter().map(|x| x.to_string()).collect::<String>();
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_counter() {
+        use super::Counter;
+        use crate::bitset::Grid;
+        use crate::Point;
+
+        let all = Grid::row(3, 1, 0);
+        let mut counter = Counter::new();
+        // every cell once, then the last 6 more times and the middle once more
+        counter.add(&all);
+        counter.add(&"..#".parse().unwrap());
+        for _ in 0..5 {
+            counter.add(&Grid::single(3, 1, Point(2, 0)));
+        }
+        counter.add(&".#.".parse().unwrap());
+        assert_eq!(counter.equals(1, &all).to_string(), "#..\n");
+        assert_eq!(counter.equals(2, &all).to_string(), ".#.\n");
+        assert_eq!(counter.equals(7, &all).to_string(), "..#\n");
+        assert!(counter.equals(0, &all).is_empty());
+        assert!(counter.equals(100, &all).is_empty());
+    }
+
+    #[test]
+    fn test_rules() {
+        use super::Rules;
+
+        let rules: Rules = "B12/S1".parse().unwrap();
+        assert_eq!(rules, Rules::eris());
+        assert_eq!(rules.to_string(), "B12/S1");
+        let life: Rules = "B3/S23".parse().unwrap();
+        assert_eq!(life.birth, vec![3]);
+        assert_eq!(life.survival, vec![2, 3]);
+        assert!("B3".parse::<Rules>().is_err());
+        assert!("S23/B3".parse::<Rules>().is_err());
+        assert!("B3/Sx".parse::<Rules>().is_err());
+    }
+}
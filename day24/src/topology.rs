@@ -0,0 +1,331 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::bitset::Grid;
+use crate::rules::{Counter, Rules};
+use crate::Point;
+
+// how the cells of a layout are connected to each other, and how the
+// bugs in them are stored and stepped on a minute
+pub trait Topology {
+    type Cell: Copy + Eq + Hash + Ord + fmt::Debug;
+    type State: Clone + Eq + fmt::Debug;
+
+    // the cell for a position on the starting grid, if it has one
+    fn cell(&self, p: Point) -> Option<Self::Cell>;
+
+    fn neighbours(&self, cell: Self::Cell) -> Vec<Self::Cell>;
+
+    fn state(&self, grid: &Grid) -> Self::State;
+
+    fn step(&self, state: &Self::State, rules: &Rules) -> Self::State;
+
+    fn bugs(&self, state: &Self::State) -> Vec<Self::Cell>;
+
+    fn count(&self, state: &Self::State) -> usize {
+        self.bugs(state).len()
+    }
+}
+
+// the number of bugs next to each cell on the same grid
+fn adjacent(grid: &Grid) -> Counter {
+    let mut counter = Counter::new();
+    for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+        counter.add(&grid.shifted(dx, dy));
+    }
+    counter
+}
+
+// a single grid, where the edges are just edges
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Flat {
+    pub size: (usize, usize),
+}
+
+impl Flat {
+    fn contains(&self, p: Point) -> bool {
+        p.0 >= 0 && p.1 >= 0 && (p.0 as usize) < self.size.0 && (p.1 as usize) < self.size.1
+    }
+}
+
+impl Topology for Flat {
+    type Cell = Point;
+    type State = Grid;
+
+    fn cell(&self, p: Point) -> Option<Point> {
+        Some(p).filter(|&p| self.contains(p))
+    }
+
+    fn neighbours(&self, cell: Point) -> Vec<Point> {
+        cell.adjacent()
+            .into_iter()
+            .filter(|&p| self.contains(p))
+            .collect()
+    }
+
+    fn state(&self, grid: &Grid) -> Grid {
+        grid.clone()
+    }
+
+    fn step(&self, state: &Grid, rules: &Rules) -> Grid {
+        rules.apply(state, &adjacent(state))
+    }
+
+    fn bugs(&self, state: &Grid) -> Vec<Point> {
+        state.cells().collect()
+    }
+
+    fn count(&self, state: &Grid) -> usize {
+        state.count()
+    }
+}
+
+// grids inside grids: the centre tile of each level holds the next level
+// down, and the edges of each level touch the tiles around the centre of
+// the level above. levels are numbered going inwards, starting at 0
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Recursive {
+    pub size: (usize, usize),
+}
+
+impl Recursive {
+    pub fn new(size: (usize, usize)) -> Result<Self, String> {
+        let (width, height) = size;
+        if width % 2 == 0 || height % 2 == 0 || width < 3 || height < 3 {
+            return Err(format!(
+                "a recursive grid needs a centre tile with tiles all round it, not {}x{}",
+                width, height
+            ));
+        }
+        Ok(Recursive { size })
+    }
+
+    pub fn centre(&self) -> Point {
+        Point((self.size.0 / 2) as isize, (self.size.1 / 2) as isize)
+    }
+
+    // the tiles around the centre, with the edges of the level inside
+    // which they touch, i.e. the tile above the centre and the top row
+    fn sides(&self) -> [(Point, Grid); 4] {
+        let (width, height) = self.size;
+        let c = self.centre();
+        [
+            (Point(c.0, c.1 - 1), Grid::row(width, height, 0)),
+            (Point(c.0, c.1 + 1), Grid::row(width, height, height - 1)),
+            (Point(c.0 - 1, c.1), Grid::column(width, height, 0)),
+            (Point(c.0 + 1, c.1), Grid::column(width, height, width - 1)),
+        ]
+    }
+
+    // the tiles along the edge of a level you'd enter moving from p
+    // into the centre
+    fn inner_edge(&self, from: Point) -> Vec<Point> {
+        let sides = self.sides();
+        let (_, edge) = sides.iter().find(|x| x.0 == from).unwrap();
+        edge.cells().collect()
+    }
+}
+
+impl Topology for Recursive {
+    type Cell = (isize, Point);
+    // only the levels with any bugs on them
+    type State = BTreeMap<isize, Grid>;
+
+    fn cell(&self, p: Point) -> Option<(isize, Point)> {
+        Flat { size: self.size }
+            .cell(p)
+            .filter(|&p| p != self.centre())
+            .map(|p| (0, p))
+    }
+
+    fn neighbours(&self, (level, cell): (isize, Point)) -> Vec<(isize, Point)> {
+        let (width, height) = (self.size.0 as isize, self.size.1 as isize);
+        let centre = self.centre();
+        let mut neighbours = vec![];
+
+        for p in cell.adjacent() {
+            if p == centre {
+                neighbours.extend(self.inner_edge(cell).into_iter().map(|x| (level + 1, x)));
+            } else if p.0 < 0 {
+                neighbours.push((level - 1, Point(centre.0 - 1, centre.1)));
+            } else if p.0 >= width {
+                neighbours.push((level - 1, Point(centre.0 + 1, centre.1)));
+            } else if p.1 < 0 {
+                neighbours.push((level - 1, Point(centre.0, centre.1 - 1)));
+            } else if p.1 >= height {
+                neighbours.push((level - 1, Point(centre.0, centre.1 + 1)));
+            } else {
+                neighbours.push((level, p));
+            }
+        }
+        neighbours
+    }
+
+    fn state(&self, grid: &Grid) -> BTreeMap<isize, Grid> {
+        let mut grid = grid.clone();
+        grid.set(self.centre(), false);
+        let mut state = BTreeMap::new();
+        if !grid.is_empty() {
+            state.insert(0, grid);
+        }
+        state
+    }
+
+    fn step(&self, state: &BTreeMap<isize, Grid>, rules: &Rules) -> BTreeMap<isize, Grid> {
+        let (width, height) = self.size;
+        let (Some(&first), Some(&last)) = (state.keys().next(), state.keys().next_back()) else {
+            return BTreeMap::new();
+        };
+        let empty = Grid::new(width, height);
+        let centre = Grid::single(width, height, self.centre());
+        let sides = self.sides();
+
+        // bugs can spread one level further out and in each minute
+        let mut next = BTreeMap::new();
+        for level in first - 1..=last + 1 {
+            let grid = state.get(&level).unwrap_or(&empty);
+            let outer = state.get(&(level - 1)).unwrap_or(&empty);
+            let inner = state.get(&(level + 1)).unwrap_or(&empty);
+
+            let mut counter = adjacent(grid);
+            for (tile, edge) in sides.iter() {
+                if outer.get(*tile) {
+                    counter.add(edge);
+                }
+                let tile = Grid::single(width, height, *tile);
+                for _ in 0..inner.and(edge).count() {
+                    counter.add(&tile);
+                }
+            }
+
+            let grid = rules.apply(grid, &counter).and_not(&centre);
+            if !grid.is_empty() {
+                next.insert(level, grid);
+            }
+        }
+        next
+    }
+
+    fn bugs(&self, state: &BTreeMap<isize, Grid>) -> Vec<(isize, Point)> {
+        state
+            .iter()
+            .flat_map(|(&level, grid)| grid.cells().map(move |p| (level, p)))
+            .collect()
+    }
+
+    fn count(&self, state: &BTreeMap<isize, Grid>) -> usize {
+        state.values().map(|x| x.count()).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{HashMap, HashSet};
+
+    use super::Topology;
+    use crate::rules::Rules;
+
+    // a step worked out cell by cell from the neighbours, to check the
+    // bit twiddling against
+    fn naive<T: Topology>(topology: &T, bugs: &[T::Cell], rules: &Rules) -> Vec<T::Cell> {
+        let alive: HashSet<T::Cell> = bugs.iter().cloned().collect();
+        let mut adjacent: HashMap<T::Cell, usize> = HashMap::new();
+        for &bug in bugs {
+            adjacent.entry(bug).or_insert(0);
+            for p in topology.neighbours(bug) {
+                *adjacent.entry(p).or_insert(0) += 1;
+            }
+        }
+        let mut next: Vec<T::Cell> = adjacent
+            .into_iter()
+            .filter(|(p, n)| {
+                if alive.contains(p) {
+                    rules.survival.contains(n)
+                } else {
+                    rules.birth.contains(n)
+                }
+            })
+            .map(|(p, _)| p)
+            .collect();
+        next.sort();
+        next
+    }
+
+    fn check<T: Topology>(topology: &T, input: &str, rules: &Rules, minutes: usize) {
+        let mut state = topology.state(&input.parse().unwrap());
+        for _ in 0..minutes {
+            let expected = naive(topology, &topology.bugs(&state), rules);
+            state = topology.step(&state, rules);
+            let mut bugs = topology.bugs(&state);
+            bugs.sort();
+            assert_eq!(bugs, expected);
+        }
+    }
+
+    const EXAMPLE: &str = "....#
+#..#.
+#..##
+..#..
+#....";
+
+    #[test]
+    fn test_flat_neighbours() {
+        use super::Flat;
+        use crate::Point;
+
+        let flat = Flat { size: (5, 5) };
+        assert_eq!(flat.neighbours(Point(2, 2)).len(), 4);
+        assert_eq!(flat.neighbours(Point(0, 0)).len(), 2);
+        assert_eq!(flat.neighbours(Point(4, 2)).len(), 3);
+        assert_eq!(flat.cell(Point(5, 0)), None);
+    }
+
+    #[test]
+    fn test_recursive_neighbours() {
+        use super::Recursive;
+        use crate::Point;
+
+        let recursive = Recursive::new((5, 5)).unwrap();
+        let count = |level, p| recursive.neighbours((level, p)).len();
+
+        // tiles 19, G, D and E, and 14 and N in the puzzle's numbering
+        assert_eq!(count(0, Point(3, 3)), 4);
+        assert_eq!(count(1, Point(1, 1)), 4);
+        assert_eq!(count(1, Point(3, 0)), 4);
+        assert_eq!(count(1, Point(4, 0)), 4);
+        assert_eq!(count(0, Point(3, 2)), 8);
+        assert_eq!(count(1, Point(3, 2)), 8);
+
+        let mut e = recursive.neighbours((1, Point(4, 0)));
+        e.sort();
+        assert_eq!(
+            e,
+            vec![
+                (0, Point(2, 1)),
+                (0, Point(3, 2)),
+                (1, Point(3, 0)),
+                (1, Point(4, 1))
+            ]
+        );
+        assert_eq!(recursive.cell(Point(2, 2)), None);
+        assert_eq!(recursive.cell(Point(2, 1)), Some((0, Point(2, 1))));
+
+        assert!(Recursive::new((4, 4)).is_err());
+        assert!(Recursive::new((1, 5)).is_err());
+    }
+
+    #[test]
+    fn test_against_neighbours() {
+        use super::{Flat, Recursive};
+
+        let wide = "#..#.....#..#\n.#...##..#...\n..#..#.#...#.\n#....#.....##\n.....#...#..#";
+        for rules in ["B12/S1", "B3/S23", "B2/S", "B123/S0123"] {
+            let rules: Rules = rules.parse().unwrap();
+            check(&Flat { size: (5, 5) }, EXAMPLE, &rules, 20);
+            check(&Flat { size: (13, 5) }, wide, &rules, 20);
+            check(&Recursive::new((5, 5)).unwrap(), EXAMPLE, &rules, 20);
+            check(&Recursive::new((13, 5)).unwrap(), wide, &rules, 10);
+        }
+    }
+}
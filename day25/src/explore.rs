@@ -0,0 +1,347 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+use intcode::Program;
+
+use crate::{password, rooms, Command, Direction, Droid, DroidError, ParseError, Room};
+
+// the items known to end the game or get the droid stuck, so there's no
+// need to try them out
+pub const BLACKLIST: [&str; 5] = [
+    "escape pod",
+    "giant electromagnet",
+    "infinite loop",
+    "molten lava",
+    "photons",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExploreError {
+    Droid(DroidError),
+    Parse(ParseError),
+    // the droid said something which wasn't a room after a move
+    NoRoom(String),
+    NoWay(String),
+    NoCheckpoint,
+    NoPassword,
+}
+
+impl fmt::Display for ExploreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExploreError::Droid(e) => write!(f, "{}", e),
+            ExploreError::Parse(e) => write!(f, "{}", e),
+            ExploreError::NoRoom(text) => write!(f, "expected a room, got {:?}", text),
+            ExploreError::NoWay(room) => write!(f, "no known way to {}", room),
+            ExploreError::NoCheckpoint => write!(f, "didn't find the security checkpoint"),
+            ExploreError::NoPassword => write!(f, "no set of items gets past the checkpoint"),
+        }
+    }
+}
+
+impl Error for ExploreError {}
+
+impl From<DroidError> for ExploreError {
+    fn from(e: DroidError) -> Self {
+        ExploreError::Droid(e)
+    }
+}
+
+impl From<ParseError> for ExploreError {
+    fn from(e: ParseError) -> Self {
+        ExploreError::Parse(e)
+    }
+}
+
+// the rooms seen so far, by name, and where their doors go
+#[derive(Clone, Debug, Default)]
+pub struct Map {
+    rooms: BTreeMap<String, Room>,
+    doors: BTreeMap<(String, Direction), String>,
+}
+
+impl Map {
+    pub fn add(&mut self, room: Room) {
+        self.rooms.insert(room.name.clone(), room);
+    }
+
+    pub fn connect(&mut self, from: &str, direction: Direction, to: &str) {
+        self.doors
+            .insert((from.to_string(), direction), to.to_string());
+        self.doors
+            .insert((to.to_string(), direction.opposite()), from.to_string());
+    }
+
+    pub fn room(&self, name: &str) -> Option<&Room> {
+        self.rooms.get(name)
+    }
+
+    pub fn rooms(&self) -> impl Iterator<Item = &Room> {
+        self.rooms.values()
+    }
+
+    pub fn door(&self, from: &str, direction: Direction) -> Option<&str> {
+        self.doors
+            .get(&(from.to_string(), direction))
+            .map(|x| x.as_str())
+    }
+
+    // the fewest moves between two rooms, through doors known to connect
+    pub fn path(&self, from: &str, to: &str) -> Option<Vec<Direction>> {
+        let mut previous: BTreeMap<&str, (&str, Direction)> = BTreeMap::new();
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+        seen.insert(from);
+        queue.push_back(from);
+
+        while let Some(room) = queue.pop_front() {
+            if room == to {
+                let mut path = vec![];
+                let mut room = room;
+                while let Some(&(before, direction)) = previous.get(room) {
+                    path.push(direction);
+                    room = before;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for ((here, direction), next) in self.doors.iter() {
+                if here == room && seen.insert(next.as_str()) {
+                    previous.insert(next, (room, *direction));
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+}
+
+// the n-th subset in an order where each differs from the one before by
+// taking or dropping just one item
+pub fn gray(n: u32) -> u32 {
+    n ^ n >> 1
+}
+
+pub struct Explorer {
+    droid: Droid,
+    map: Map,
+    // the room the droid's in
+    room: String,
+    inventory: BTreeSet<String>,
+    blacklist: BTreeSet<String>,
+    // the security checkpoint, and the way from it to the pressure
+    // sensitive floor, which throws the droid back out
+    checkpoint: Option<(String, Direction)>,
+}
+
+impl Explorer {
+    pub fn new(program: Program) -> Result<Self, ExploreError> {
+        let mut droid = Droid::new(program);
+        let text = droid.start()?;
+        let room = rooms(&text)?.pop().ok_or(ExploreError::NoRoom(text))?;
+        let mut map = Map::default();
+        let name = room.name.clone();
+        map.add(room);
+
+        Ok(Explorer {
+            droid,
+            map,
+            room: name,
+            inventory: BTreeSet::new(),
+            blacklist: BLACKLIST.iter().map(|x| x.to_string()).collect(),
+            checkpoint: None,
+        })
+    }
+
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+
+    pub fn room(&self) -> &str {
+        &self.room
+    }
+
+    pub fn inventory(&self) -> &BTreeSet<String> {
+        &self.inventory
+    }
+
+    pub fn blacklist(&self) -> &BTreeSet<String> {
+        &self.blacklist
+    }
+
+    pub fn checkpoint(&self) -> Option<&(String, Direction)> {
+        self.checkpoint.as_ref()
+    }
+
+    // every room the droid went through on the way, ending up in the last
+    fn go(&mut self, direction: Direction) -> Result<Vec<Room>, ExploreError> {
+        let text = self.droid.send(&Command::Move(direction))?;
+        let rooms = rooms(&text)?;
+        match rooms.last() {
+            Some(room) => self.room = room.name.clone(),
+            None => return Err(ExploreError::NoRoom(text)),
+        }
+        Ok(rooms)
+    }
+
+    fn take(&mut self, item: &str) -> Result<(), ExploreError> {
+        self.droid.send(&Command::Take(item.to_string()))?;
+        self.inventory.insert(item.to_string());
+        Ok(())
+    }
+
+    fn drop(&mut self, item: &str) -> Result<(), ExploreError> {
+        self.droid.send(&Command::Drop(item.to_string()))?;
+        self.inventory.remove(item);
+        Ok(())
+    }
+
+    // tries picking the item up with a copy of the droid, and checks the
+    // copy can still get around afterwards
+    fn is_safe(&self, item: &str) -> bool {
+        if self.blacklist.contains(item) {
+            return false;
+        }
+        let mut droid = self.droid.clone();
+        if droid.send(&Command::Take(item.to_string())).is_err() || droid.is_halted() {
+            return false;
+        }
+        let doors = self
+            .map
+            .room(&self.room)
+            .map_or(vec![], |x| x.doors.clone());
+        match doors.first() {
+            Some(&door) => match droid.send(&Command::Move(door)) {
+                Ok(text) => !droid.is_halted() && !text.contains("can't move"),
+                Err(_) => false,
+            },
+            None => true,
+        }
+    }
+
+    // goes through every door on the ship, picking up everything which
+    // is safe on the way, and comes back to where it started
+    pub fn explore(&mut self) -> Result<(), ExploreError> {
+        let here = self.room.clone();
+        let room = self.map.room(&here).unwrap().clone();
+
+        for item in room.items.iter() {
+            if self.is_safe(item) {
+                self.take(item)?;
+            } else {
+                self.blacklist.insert(item.clone());
+            }
+        }
+
+        for &door in room.doors.iter() {
+            if self.map.door(&here, door).is_some() {
+                continue;
+            }
+            let mut rooms = self.go(door)?;
+            let there = rooms.pop().unwrap();
+            if there.name == here {
+                // thrown straight back, so the door goes to the floor
+                if let Some(floor) = rooms.pop() {
+                    self.map.add(floor);
+                }
+                self.checkpoint = Some((here.clone(), door));
+                continue;
+            }
+
+            let name = there.name.clone();
+            let new = self.map.room(&name).is_none();
+            self.map.add(there);
+            self.map.connect(&here, door, &name);
+            if new {
+                self.explore()?;
+            }
+            self.go(door.opposite())?;
+        }
+        Ok(())
+    }
+
+    pub fn travel(&mut self, to: &str) -> Result<(), ExploreError> {
+        let path = self
+            .map
+            .path(&self.room, to)
+            .ok_or_else(|| ExploreError::NoWay(to.to_string()))?;
+        for direction in path {
+            self.go(direction)?;
+        }
+        Ok(())
+    }
+
+    // goes to the checkpoint and tries every subset of the items held on
+    // the floor until one weighs just right
+    pub fn crack(&mut self) -> Result<String, ExploreError> {
+        let (checkpoint, door) = self.checkpoint.clone().ok_or(ExploreError::NoCheckpoint)?;
+        self.travel(&checkpoint)?;
+
+        let items: Vec<String> = self.inventory.iter().cloned().collect();
+        let mut held = (1 << items.len()) - 1;
+        for n in 0..1 << items.len() {
+            let next = gray(n);
+            for (i, item) in items.iter().enumerate() {
+                match (held >> i & 1, next >> i & 1) {
+                    (0, 1) => self.take(item)?,
+                    (1, 0) => self.drop(item)?,
+                    _ => (),
+                }
+            }
+            held = next;
+
+            let text = self.droid.send(&Command::Move(door))?;
+            if let Some(password) = password(&text) {
+                return Ok(password);
+            }
+        }
+        Err(ExploreError::NoPassword)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test_gray() {
+        use super::gray;
+
+        let mut seen = [false; 32];
+        for n in 0..32 {
+            seen[gray(n) as usize] = true;
+            if n > 0 {
+                assert_eq!((gray(n) ^ gray(n - 1)).count_ones(), 1);
+            }
+        }
+        assert!(seen.iter().all(|&x| x));
+    }
+
+    #[test]
+    fn test_path() {
+        use super::Map;
+        use crate::Direction::{East, North, South, West};
+        use crate::Room;
+
+        let mut map = Map::default();
+        for name in ["Hull Breach", "Kitchen", "Hallway", "Observatory"] {
+            map.add(Room {
+                name: name.to_string(),
+                description: String::new(),
+                doors: vec![],
+                items: vec![],
+            });
+        }
+        map.connect("Hull Breach", North, "Kitchen");
+        map.connect("Kitchen", East, "Hallway");
+        map.connect("Hallway", South, "Observatory");
+        map.connect("Hull Breach", West, "Observatory");
+
+        assert_eq!(map.door("Hallway", West), Some("Kitchen"));
+        assert_eq!(map.path("Hull Breach", "Hallway"), Some(vec![North, East]));
+        assert_eq!(map.path("Kitchen", "Observatory"), Some(vec![South, West]));
+        assert_eq!(map.path("Hallway", "Hallway"), Some(vec![]));
+        assert_eq!(map.path("Hallway", "Engineering"), None);
+        assert_eq!(map.rooms().count(), 4);
+    }
+}
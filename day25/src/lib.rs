@@ -0,0 +1,423 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::{Answer, Solution};
+use intcode::{Interupt, Program};
+
+pub mod explore;
+
+// more than any room description, so the droid's stuck printing forever
+const RUNAWAY: usize = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "north" | "n" => Ok(Direction::North),
+            "south" | "s" => Ok(Direction::South),
+            "east" | "e" => Ok(Direction::East),
+            "west" | "w" => Ok(Direction::West),
+            _ => Err(format!("unknown direction {:?}", input)),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::North => write!(f, "north"),
+            Direction::South => write!(f, "south"),
+            Direction::East => write!(f, "east"),
+            Direction::West => write!(f, "west"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Move(Direction),
+    Take(String),
+    Drop(String),
+    Inventory,
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if let Some(item) = input.strip_prefix("take ") {
+            Ok(Command::Take(item.trim().to_string()))
+        } else if let Some(item) = input.strip_prefix("drop ") {
+            Ok(Command::Drop(item.trim().to_string()))
+        } else if input == "inv" || input == "i" {
+            Ok(Command::Inventory)
+        } else {
+            input
+                .parse()
+                .map(Command::Move)
+                .map_err(|_| format!("unknown command {:?}", input))
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::Move(direction) => write!(f, "{}", direction),
+            Command::Take(item) => write!(f, "take {}", item),
+            Command::Drop(item) => write!(f, "drop {}", item),
+            Command::Inventory => write!(f, "inv"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Room {
+    pub name: String,
+    pub description: String,
+    pub doors: Vec<Direction>,
+    pub items: Vec<String>,
+}
+
+impl FromStr for Room {
+    type Err = ParseError;
+
+    // a room as the droid describes it:
+    //
+    //   == Hull Breach ==
+    //   You got in through a hole in the floor here.
+    //
+    //   Doors here lead:
+    //   - north
+    //
+    //   Items here:
+    //   - mug
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let name = match lines.next() {
+            Some((_, line)) if line.starts_with("== ") && line.ends_with(" ==") => {
+                line[3..line.len() - 3].to_string()
+            }
+            Some((line, _)) => {
+                return Err(ParseError {
+                    line,
+                    message: "expected a room name".to_string(),
+                })
+            }
+            None => {
+                return Err(ParseError {
+                    line: 1,
+                    message: "no room".to_string(),
+                })
+            }
+        };
+
+        let mut room = Room {
+            name,
+            description: String::new(),
+            doors: vec![],
+            items: vec![],
+        };
+        let mut list = None;
+        for (i, line) in lines {
+            match (line, list) {
+                ("Doors here lead:", _) => list = Some(true),
+                ("Items here:", _) => list = Some(false),
+                ("Command?", _) => break,
+                (_, Some(doors)) if line.starts_with("- ") => {
+                    if doors {
+                        room.doors.push(
+                            line[2..]
+                                .parse()
+                                .map_err(|message| ParseError { line: i, message })?,
+                        );
+                    } else {
+                        room.items.push(line[2..].to_string());
+                    }
+                }
+                (_, None) => {
+                    if !room.description.is_empty() {
+                        room.description.push('\n');
+                    }
+                    room.description.push_str(line);
+                }
+                // anything else is the droid saying what happens next
+                (_, Some(_)) => break,
+            }
+        }
+        Ok(room)
+    }
+}
+
+// every room described in some of the droid's output, in order, as it
+// can go through more than one room in a move
+pub fn rooms(text: &str) -> Result<Vec<Room>, ParseError> {
+    let lines: Vec<&str> = text.lines().collect();
+    let starts: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("== "))
+        .collect();
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let end = starts.get(n + 1).copied().unwrap_or(lines.len());
+            lines[start..end]
+                .join("\n")
+                .parse()
+                .map_err(|e: ParseError| ParseError {
+                    line: start + e.line,
+                    message: e.message,
+                })
+        })
+        .collect()
+}
+
+// the code the droid's given once it gets past the checkpoint
+pub fn password(text: &str) -> Option<String> {
+    let start = text.find("typing ")? + "typing ".len();
+    let code: String = text[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    Some(code).filter(|x| !x.is_empty())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DroidError {
+    Halted,
+    // the droid kept on printing without ever asking for another command
+    Runaway,
+}
+
+impl fmt::Display for DroidError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DroidError::Halted => write!(f, "the droid has stopped"),
+            DroidError::Runaway => write!(f, "the droid won't stop talking"),
+        }
+    }
+}
+
+impl Error for DroidError {}
+
+// the droid's program, driven one command at a time. cloning it gives a
+// copy of the droid to try things out on
+#[derive(Clone)]
+pub struct Droid {
+    // where the program stopped last
+    state: Interupt,
+}
+
+impl Droid {
+    pub fn new(program: Program) -> Self {
+        Droid {
+            state: program.execute(),
+        }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        matches!(self.state, Interupt::Halt)
+    }
+
+    // feeds the program the input, and gives back what it printed before
+    // wanting more or halting
+    fn run(&mut self, input: &str) -> Result<String, DroidError> {
+        if self.is_halted() {
+            return Err(DroidError::Halted);
+        }
+        let mut state = std::mem::replace(&mut self.state, Interupt::Halt);
+        let mut input = input.bytes();
+        let mut text = String::new();
+        loop {
+            state = match state {
+                Interupt::Halt => return Ok(text),
+                Interupt::Input(waiting) => match input.next() {
+                    Some(c) => waiting.input(c as isize).execute(),
+                    None => {
+                        self.state = Interupt::Input(waiting);
+                        return Ok(text);
+                    }
+                },
+                Interupt::Output(output) => {
+                    let (program, value) = output.receive();
+                    text.push(value as u8 as char);
+                    if text.len() > RUNAWAY {
+                        return Err(DroidError::Runaway);
+                    }
+                    program.execute()
+                }
+            };
+        }
+    }
+
+    // what the droid says when it's first switched on
+    pub fn start(&mut self) -> Result<String, DroidError> {
+        self.run("")
+    }
+
+    pub fn send(&mut self, command: &Command) -> Result<String, DroidError> {
+        self.run(&format!("{}\n", command))
+    }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Program;
+
+    fn parse(&self, input: &str) -> Result<Program, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    // the password for the main airlock, from getting past the checkpoint
+    // holding just the right items
+    fn part1(&self, program: &Program) -> Answer {
+        let mut explorer = explore::Explorer::new(program.clone())?;
+        explorer.explore()?;
+        Ok(explorer.crack()?)
+    }
+
+    // there's no second puzzle on the last day, so nothing to work out
+    fn part2(&self, _: &Program) -> Answer {
+        Ok("none, it's the last day".to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    const HULL_BREACH: &str = "
+
+== Hull Breach ==
+You got in through a hole in the floor here. To keep your ship from also freezing, the hole has been sealed.
+
+Doors here lead:
+- north
+- east
+- south
+
+Items here:
+- mug
+
+Command?
+";
+
+    #[test]
+    fn test_room() {
+        use super::{Direction, Room};
+
+        let room: Room = HULL_BREACH.parse().unwrap();
+        assert_eq!(room.name, "Hull Breach");
+        assert!(room.description.starts_with("You got in"));
+        assert_eq!(
+            room.doors,
+            vec![Direction::North, Direction::East, Direction::South]
+        );
+        assert_eq!(room.items, vec!["mug".to_string()]);
+
+        assert_eq!(
+            "== A ==\nDoors here lead:\n- up"
+                .parse::<Room>()
+                .unwrap_err()
+                .to_string(),
+            "line 3: unknown direction \"up\""
+        );
+        assert!("Command?".parse::<Room>().is_err());
+    }
+
+    #[test]
+    fn test_rooms() {
+        use super::rooms;
+
+        let text = "
+
+== Pressure-Sensitive Floor ==
+Analyzing...
+
+Doors here lead:
+- west
+
+A loud, robotic voice says \"Alert! Droids on this ship are heavier than the detected value!\" and you are ejected back to the checkpoint.
+
+
+
+== Security Checkpoint ==
+In the next room, a pressure-sensitive floor will verify your identity.
+
+Doors here lead:
+- north
+- east
+
+Command?
+";
+        let rooms = rooms(text).unwrap();
+        let names: Vec<&str> = rooms.iter().map(|x| x.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Pressure-Sensitive Floor", "Security Checkpoint"]
+        );
+        assert_eq!(rooms[1].doors.len(), 2);
+        assert_eq!(rooms[0].description, "Analyzing...");
+    }
+
+    #[test]
+    fn test_commands() {
+        use super::{password, Command, Direction};
+
+        assert_eq!("n".parse(), Ok(Command::Move(Direction::North)));
+        assert_eq!(
+            "take space heater".parse(),
+            Ok(Command::Take("space heater".to_string()))
+        );
+        assert!("jump".parse::<Command>().is_err());
+        assert_eq!(Command::Drop("mug".to_string()).to_string(), "drop mug");
+        assert_eq!(Command::Move(Direction::West).to_string(), "west");
+
+        assert_eq!(
+            password("You should be able to get in by typing 2424308736 on the keypad"),
+            Some("2424308736".to_string())
+        );
+        assert_eq!(password("typing nothing"), None);
+    }
+}
@@ -0,0 +1,66 @@
+use std::env;
+use std::io::{self, BufRead};
+
+use intcode::Program;
+
+use day25::explore::Explorer;
+use day25::{Command, Day25, Droid};
+
+// drives the droid by hand, a command per line
+fn play(program: Program) {
+    let mut droid = Droid::new(program);
+    print!("{}", droid.start().unwrap());
+    for line in io::stdin().lock().lines() {
+        let command: Command = match line.unwrap().parse() {
+            Ok(command) => command,
+            Err(e) => {
+                println!("{}", e);
+                continue;
+            }
+        };
+        match droid.send(&command) {
+            Ok(text) => print!("{}", text),
+            Err(e) => println!("{}", e),
+        }
+        if droid.is_halted() {
+            break;
+        }
+    }
+}
+
+fn main() {
+    // `day25 --play` to explore the ship yourself, and `day25 --map` to
+    // show what the droid found on the way
+    let mut show = false;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--play" => {
+                let input = aoc::read_input(25).unwrap();
+                return play(input.parse().unwrap_or_else(|e| panic!("{}", e)));
+            }
+            "--map" => show = true,
+            _ => panic!("unexpected argument {}", arg),
+        }
+    }
+
+    let program = aoc::solve(25, &Day25);
+    if !show {
+        return;
+    }
+
+    let mut explorer = Explorer::new(program).unwrap_or_else(|e| panic!("{}", e));
+    explorer.explore().unwrap_or_else(|e| panic!("{}", e));
+    for room in explorer.map().rooms() {
+        let doors: Vec<String> = room
+            .doors
+            .iter()
+            .map(|&d| match explorer.map().door(&room.name, d) {
+                Some(to) => format!("{} to {}", d, to),
+                None => format!("{} to ?", d),
+            })
+            .collect();
+        println!("{}: {}", room.name, doors.join(", "));
+    }
+    println!("holding {:?}", explorer.inventory());
+    println!("left {:?}", explorer.blacklist());
+}
@@ -1,6 +1,8 @@
-fn divmod(value: isize, quotient: isize) -> (isize, isize) {
-    (value / quotient, value % quotient)
-}
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_utils::divmod;
 
 #[derive(Debug, Clone, Copy)]
 enum Var {
@@ -72,7 +74,7 @@ impl Program {
             1 => {
                 let vars = Var::parse(&self.memory[self.pointer + 1..self.pointer + 4], mask);
                 Op::Add(
-                    *vars.get(0).unwrap(),
+                    *vars.first().unwrap(),
                     *vars.get(1).unwrap(),
                     *vars.get(2).unwrap(),
                 )
@@ -80,33 +82,33 @@ impl Program {
             2 => {
                 let vars = Var::parse(&self.memory[self.pointer + 1..self.pointer + 4], mask);
                 Op::Multiply(
-                    *vars.get(0).unwrap(),
+                    *vars.first().unwrap(),
                     *vars.get(1).unwrap(),
                     *vars.get(2).unwrap(),
                 )
             }
             3 => Op::Input(
                 *Var::parse(&self.memory[self.pointer + 1..self.pointer + 2], mask)
-                    .get(0)
+                    .first()
                     .unwrap(),
             ),
             4 => Op::Output(
                 *Var::parse(&self.memory[self.pointer + 1..self.pointer + 2], mask)
-                    .get(0)
+                    .first()
                     .unwrap(),
             ),
             5 => {
                 let vars = Var::parse(&self.memory[self.pointer + 1..self.pointer + 3], mask);
-                Op::JumpIfTrue(*vars.get(0).unwrap(), *vars.get(1).unwrap())
+                Op::JumpIfTrue(*vars.first().unwrap(), *vars.get(1).unwrap())
             }
             6 => {
                 let vars = Var::parse(&self.memory[self.pointer + 1..self.pointer + 3], mask);
-                Op::JumpIfFalse(*vars.get(0).unwrap(), *vars.get(1).unwrap())
+                Op::JumpIfFalse(*vars.first().unwrap(), *vars.get(1).unwrap())
             }
             7 => {
                 let vars = Var::parse(&self.memory[self.pointer + 1..self.pointer + 4], mask);
                 Op::LessThan(
-                    *vars.get(0).unwrap(),
+                    *vars.first().unwrap(),
                     *vars.get(1).unwrap(),
                     *vars.get(2).unwrap(),
                 )
@@ -114,14 +116,14 @@ impl Program {
             8 => {
                 let vars = Var::parse(&self.memory[self.pointer + 1..self.pointer + 4], mask);
                 Op::Equal(
-                    *vars.get(0).unwrap(),
+                    *vars.first().unwrap(),
                     *vars.get(1).unwrap(),
                     *vars.get(2).unwrap(),
                 )
             }
             9 => Op::AdjustOffset(
                 *Var::parse(&self.memory[self.pointer + 1..self.pointer + 2], mask)
-                    .get(0)
+                    .first()
                     .unwrap(),
             ),
             99 => Op::Halt,
@@ -236,18 +238,44 @@ impl Program {
     }
 }
 
-impl From<String> for Program {
-    fn from(input: String) -> Self {
-        Program::new(
-            input
-                .trim()
-                .split(",")
-                .map(|x| x.parse().unwrap())
-                .collect(),
+impl FromStr for Program {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        input
+            .trim()
+            .split(",")
+            .enumerate()
+            .map(|(position, x)| {
+                x.trim().parse().map_err(|_| ParseError {
+                    position,
+                    value: x.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Program::new)
+    }
+}
+
+// a value in the program which isn't a number, counting from 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub value: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected a number at position {}, found {:?}",
+            self.position, self.value
         )
     }
 }
 
+impl Error for ParseError {}
+
 #[derive(Clone)]
 pub struct InputState {
     program: Program,
@@ -273,6 +301,18 @@ impl OutputState {
     }
 }
 
+// the program wasn't waiting for input when it was given some
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputError;
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the program isn't waiting for input")
+    }
+}
+
+impl Error for InputError {}
+
 pub struct Computer {
     program: Option<Program>,
 }
@@ -284,11 +324,11 @@ impl Computer {
         }
     }
 
-    pub fn input(&mut self, value: isize) -> Result<(), ()> {
+    pub fn input(&mut self, value: isize) -> Result<(), InputError> {
         let program = self.program.take();
         let input = match program.unwrap().execute() {
             Interupt::Input(input) => input,
-            _ => return Err(()),
+            _ => return Err(InputError),
         };
         self.program = Some(input.input(value));
         Ok(())
@@ -359,4 +399,22 @@ mod tests {
 
         assert_eq!(outputs, raw);
     }
+
+    #[test]
+    fn test_parse() {
+        use super::{Computer, ParseError, Program};
+
+        let program: Program = "104,7,99\n".parse().unwrap();
+        let outputs: Vec<isize> = Computer::new(program).collect();
+        assert_eq!(outputs, vec![7]);
+
+        assert_eq!(
+            "1,2,x".parse::<Program>().err(),
+            Some(ParseError {
+                position: 2,
+                value: "x".to_string()
+            })
+        );
+        assert!("".parse::<Program>().is_err());
+    }
 }
@@ -0,0 +1,125 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+use aoc::Run;
+
+fn solutions() -> Vec<Box<dyn Run>> {
+    vec![
+        Box::new(day01::Day01),
+        Box::new(day02::Day02),
+        Box::new(day03::Day03),
+        Box::new(day04::Day04),
+        Box::new(day05::Day05),
+        Box::new(day06::Day06),
+        Box::new(day07::Day07),
+        Box::new(day08::Day08),
+        Box::new(day09::Day09),
+        Box::new(day10::Day10),
+        Box::new(day11::Day11),
+        Box::new(day12::Day12),
+        Box::new(day13::Day13),
+        Box::new(day14::Day14),
+        Box::new(day15::Day15),
+        Box::new(day16::Day16),
+        Box::new(day17::Day17),
+        Box::new(day18::Day18),
+        Box::new(day19::Day19),
+        Box::new(day20::Day20),
+        Box::new(day21::Day21),
+        Box::new(day22::Day22),
+        Box::new(day23::Day23),
+        Box::new(day24::Day24),
+        Box::new(day25::Day25),
+    ]
+}
+
+struct Options {
+    day: Option<u8>,
+    parts: Vec<u8>,
+    input: Option<PathBuf>,
+}
+
+fn usage() -> ! {
+    eprintln!("usage: runner [--day N [--input PATH]] [--part P]");
+    process::exit(2);
+}
+
+fn options() -> Options {
+    let mut options = Options {
+        day: None,
+        parts: vec![1, 2],
+        input: None,
+    };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--day" => options.day = Some(value.parse().unwrap_or_else(|_| usage())),
+            "--part" => options.parts = vec![value.parse().unwrap_or_else(|_| usage())],
+            "--input" => options.input = Some(PathBuf::from(value)),
+            _ => usage(),
+        }
+    }
+    if options.input.is_some() && options.day.is_none() {
+        usage();
+    }
+    options
+}
+
+fn main() {
+    let options = options();
+    let solutions = solutions();
+    let days: Vec<u8> = match options.day {
+        Some(day) if day >= 1 && day as usize <= solutions.len() => vec![day],
+        Some(day) => {
+            eprintln!("there's no day {}", day);
+            process::exit(1);
+        }
+        None => (1..=solutions.len() as u8).collect(),
+    };
+
+    let mut total = Duration::default();
+    let mut failed = false;
+    for day in days {
+        println!("day {}", day);
+        let path = options
+            .input
+            .clone()
+            .unwrap_or_else(|| aoc::input_path(day));
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("can't read {}: {}", path.display(), e);
+                failed = true;
+                continue;
+            }
+        };
+        match solutions[day as usize - 1].run(&input, &options.parts) {
+            Ok(report) => {
+                println!("parse ({:?})", report.parse);
+                for timed in report.parts.iter() {
+                    if timed.answer.is_ok() {
+                        print!("{}", timed);
+                    } else {
+                        eprint!("{}", timed);
+                        failed = true;
+                    }
+                }
+                total += report.total();
+            }
+            Err(e) => {
+                eprintln!("can't parse {}: {}", path.display(), e);
+                failed = true;
+            }
+        }
+    }
+    if options.day.is_none() {
+        println!("total ({:?})", total);
+    }
+    if failed {
+        process::exit(1);
+    }
+}
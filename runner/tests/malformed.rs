@@ -0,0 +1,40 @@
+use std::env;
+use std::fs;
+use std::process::Command;
+
+// every intcode day should turn a bad program down rather than panic
+#[test]
+fn test_malformed_intcode() {
+    let path = env::temp_dir().join("runner-malformed-intcode.txt");
+    fs::write(&path, "1,2,x\n").unwrap();
+
+    for day in [7, 9, 11, 13, 15, 17, 19, 21, 23, 25] {
+        let output = Command::new(env!("CARGO_BIN_EXE_runner"))
+            .args(["--day", &day.to_string(), "--input"])
+            .arg(&path)
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(1), "day {}: {}", day, stderr);
+        assert!(stderr.contains("can't parse"), "day {}: {}", day, stderr);
+    }
+}
+
+// a part which can't be answered fails the run, though the other part's
+// answer is still given
+#[test]
+fn test_failed_part() {
+    let path = env::temp_dir().join("runner-even-grid.txt");
+    fs::write(&path, "....\n.#..\n....\n....\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_runner"))
+        .args(["--day", "24", "--input"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert!(stdout.contains("part 1: "), "{}", stdout);
+    assert!(stderr.contains("part 2: error: "), "{}", stderr);
+}